@@ -0,0 +1,167 @@
+use std::{error::Error, path::{Path, PathBuf}, time::Duration};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use common::{json, read_input, InputSource, Report, Solution, Value};
+use crate::output;
+
+/**
+ * What happened when one input file was run
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRow {
+    pub path: PathBuf,
+    pub outcome: Result<Report, String>
+}
+
+/**
+ * Finds the input files to run.  A directory means every file directly inside it; anything else is taken as a
+ * glob pattern such as `inputs/day9/input-?.txt`.  Either way the files come back sorted, and it is an error for there to be none
+ */
+pub fn find_inputs(pattern:&str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let dir = Path::new(pattern);
+    let mut paths:Vec<PathBuf> = if dir.is_dir() {
+        std::fs::read_dir(dir)
+            .map_err(|e| format!("could not read {}: {}", dir.display(), e))?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<PathBuf>, std::io::Error>>()?
+    } else {
+        glob::glob(pattern)
+            .map_err(|e| format!("'{}' is not a valid glob pattern: {}", pattern, e))?
+            .collect::<Result<Vec<PathBuf>, glob::GlobError>>()?
+    };
+    paths.retain(|path| path.is_file());
+    paths.sort();
+
+    if paths.is_empty() {
+        Err(format!("no input files found at {}", pattern).into())
+    } else {
+        Ok(paths)
+    }
+}
+
+/**
+ * Runs the solution against each of the files in parallel.  A file that cannot be read or solved gets an error
+ * in its row rather than stopping the rest of the batch
+ */
+pub fn run_batch(solution:&dyn Solution, paths:&[PathBuf], parts:&[u8]) -> Vec<BatchRow> {
+    paths.par_iter().map(|path| {
+        let outcome = read_input(path)
+            .and_then(|content| solution.run(&content, parts, false))
+            .map_err(|e| e.to_string());
+        BatchRow { path: path.clone(), outcome }
+    }).collect()
+}
+
+fn millis(d:&Duration) -> String {
+    format!("{:.3}", d.as_secs_f64() * 1000.0)
+}
+
+/**
+ * Lays the rows out as a table of answers and timings (in milliseconds), one line per file.  The errors from
+ * failed files are written out in full underneath, as parse errors run over several lines
+ */
+pub fn render_table(rows:&[BatchRow], parts:&[u8]) -> String {
+    let mut header = vec!["input".to_string(), "status".to_string()];
+    header.extend(parts.iter().map(|p| format!("part {}", p)));
+    header.push("parse ms".to_string());
+    header.extend(parts.iter().map(|p| format!("part {} ms", p)));
+
+    let table:Vec<Vec<String>> = std::iter::once(header).chain(rows.iter().map(|row| {
+        let mut cells = vec![row.path.display().to_string()];
+        match &row.outcome {
+            Ok(report)=>{
+                cells.push("ok".to_string());
+                cells.extend(report.answers.iter().map(|a| a.text.clone()));
+                cells.push(millis(&report.parse_time));
+                cells.extend(report.answers.iter().map(|a| millis(&a.elapsed)));
+            },
+            Err(_)=>{
+                cells.push("FAILED".to_string());
+                cells.extend(std::iter::repeat_n("-".to_string(), parts.len() * 2 + 1));
+            }
+        }
+        cells
+    })).collect();
+
+    let widths:Vec<usize> = (0..table[0].len())
+        .map(|col| table.iter().map(|cells| cells[col].chars().count()).max().unwrap_or(0))
+        .collect();
+    let mut lines:Vec<String> = table.iter().map(|cells| {
+        let padded:Vec<String> = cells.iter().zip(widths.iter()).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        padded.join("  ").trim_end().to_string()
+    }).collect();
+
+    for row in rows.iter() {
+        if let Err(e) = &row.outcome {
+            lines.push(String::new());
+            lines.push(format!("{}: {}", row.path.display(), e));
+        }
+    }
+    lines.join("\n")
+}
+
+pub fn render_json(day:u8, rows:&[BatchRow]) -> Value {
+    let files:Vec<Value> = rows.iter().map(|row| {
+        let source = InputSource::File(row.path.clone());
+        match &row.outcome {
+            Ok(report)=>output::render_json(day, &source, report, None),
+            Err(e)=>json!({ "day": day, "input": output::describe_source(&source), "error": e })
+        }
+    }).collect();
+    json!(files)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::Answer;
+
+    fn ok_row(path:&str, answer:&str) -> BatchRow {
+        let report = Report {
+            parse_time: Duration::from_micros(1500),
+            answers: vec![Answer { part: 1, value: json!(answer), text: answer.to_string(), elapsed: Duration::from_millis(2) }],
+            details: Value::Null
+        };
+        BatchRow { path: PathBuf::from(path), outcome: Ok(report) }
+    }
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            ok_row("a.txt", "12345"),
+            BatchRow { path: PathBuf::from("broken.txt"), outcome: Err("line 1, column 1: bad".to_string()) }
+        ];
+        assert_eq!(render_table(&rows, &[1]), "input       status  part 1  parse ms  part 1 ms
+a.txt       ok      12345   1.500     2.000
+broken.txt  FAILED  -       -         -
+
+broken.txt: line 1, column 1: bad");
+    }
+
+    #[test]
+    fn test_render_json() {
+        let rows = vec![ok_row("a.txt", "7"), BatchRow { path: PathBuf::from("b.txt"), outcome: Err("bad".to_string()) }];
+        let json = render_json(3, &rows);
+        assert_eq!(json[0]["answers"]["part1"]["answer"], json!("7"));
+        assert_eq!(json[1], json!({ "day": 3, "input": "b.txt", "error": "bad" }));
+    }
+
+    #[test]
+    fn test_find_and_run() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("good.txt"), day1::EXAMPLE).unwrap();
+        std::fs::write(dir.join("bad.txt"), "L68\nX12\n").unwrap();
+
+        let paths = find_inputs(dir.to_str().unwrap()).unwrap();
+        assert_eq!(paths, vec![dir.join("bad.txt"), dir.join("good.txt")]);
+        assert_eq!(find_inputs(dir.join("g*.txt").to_str().unwrap()).unwrap(), vec![dir.join("good.txt")]);
+        assert!(find_inputs(dir.join("*.json").to_str().unwrap()).is_err());
+
+        let rows = run_batch(&day1::Day1, &paths, &[1, 2]);
+        assert!(rows[0].outcome.as_ref().unwrap_err().starts_with("line 2"));
+        let answers:Vec<String> = rows[1].outcome.as_ref().unwrap().answers.iter().map(|a| a.text.clone()).collect();
+        assert_eq!(answers, vec!["3", "6"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use std::{error::Error, fmt::Display, time::Duration};
+use common::Solution;
+
+/**
+ * Summary statistics over a set of timings
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration
+}
+
+impl Stats {
+    pub fn from_samples(samples:&[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid-1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs:Vec<f64> = sorted.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        //Population standard deviation; we have every sample we are going to get
+        let variance = secs.iter().map(|s| (s-mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Some(Stats {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt())
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mean {:>10.3?}  median {:>10.3?}  stddev {:>10.3?}", self.mean, self.median, self.stddev)
+    }
+}
+
+/**
+ * Timings for each phase of a day, gathered over a number of iterations
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats
+}
+
+/**
+ * Runs the solution over the input `iterations` times, timing parse, part 1 and part 2 separately
+ */
+pub fn bench(solution:&dyn Solution, input:&str, iterations:usize) -> Result<BenchResult, Box<dyn Error>> {
+    if iterations==0 {
+        return Err("need at least one iteration to benchmark".into())
+    }
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let report = solution.run(input, &[1, 2], false)?;
+        parse.push(report.parse_time);
+        part1.push(report.answers[0].elapsed);
+        part2.push(report.answers[1].elapsed);
+    }
+
+    //unwraps are safe, as we've checked that there is at least one sample
+    Ok(BenchResult {
+        iterations,
+        parse: Stats::from_samples(&parse).unwrap(),
+        part1: Stats::from_samples(&part1).unwrap(),
+        part2: Stats::from_samples(&part2).unwrap()
+    })
+}
+
+/**
+ * As `bench`, but with every rayon parallel iterator inside the solution restricted to the given number of threads.
+ * One thread means the days' parallel iterators run sequentially
+ */
+pub fn bench_with_threads(solution:&dyn Solution, input:&str, iterations:usize, threads:usize) -> Result<BenchResult, Box<dyn Error>> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build()?;
+    //errors can't cross back over from the pool's threads, so bring them back as text
+    pool.install(|| bench(solution, input, iterations).map_err(|e| e.to_string()))
+        .map_err(|e| e.into())
+}
+
+pub fn render(label:&str, result:&BenchResult) -> String {
+    format!("{} ({} iterations)\n  parse  {}\n  part 1 {}\n  part 2 {}",
+        label, result.iterations, result.parse, result.part1, result.part2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(n:u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(8)]).unwrap();
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean.as_micros(), 5000);
+        assert_eq!(stats.stddev.as_micros(), 2236);     //sqrt(5) ms
+
+        let stats = Stats::from_samples(&[ms(3), ms(1), ms(100)]).unwrap();
+        assert_eq!(stats.median, ms(3));
+    }
+
+    #[test]
+    fn test_bench() {
+        let solution = crate::days::lookup(1, true).unwrap();
+        let input = solution.example().input;
+        let result = bench(solution.as_ref(), input, 3).unwrap();
+        assert_eq!(result.iterations, 3);
+        assert!(bench(solution.as_ref(), input, 0).is_err());
+
+        let result = bench_with_threads(solution.as_ref(), input, 2, 1).unwrap();
+        assert_eq!(result.iterations, 2);
+    }
+}
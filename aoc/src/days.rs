@@ -0,0 +1,80 @@
+use common::Solution;
+
+/**
+ * Looks up the solution for the given day.  Some days need configuring differently for their worked example,
+ * so we need to know which kind of input it is going to be given
+ */
+pub fn lookup(number:u8, example:bool) -> Option<Box<dyn Solution>> {
+    match number {
+        1=>Some(Box::new(day1::Day1)),
+        2=>Some(Box::new(day2::Day2)),
+        3=>Some(Box::new(day3::Day3)),
+        4=>Some(Box::new(day4::Day4)),
+        5=>Some(Box::new(day5::Day5)),
+        6=>Some(Box::new(day6::Day6)),
+        7=>Some(Box::new(day7::Day7)),
+        8=>Some(Box::new(if example {
+            day8::Day8::for_example()
+        } else {
+            day8::Day8::default()
+        })),
+        9=>Some(Box::new(day9::Day9)),
+        _=>None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert!(lookup(5, false).is_some());
+        assert!(lookup(0, false).is_none());
+        assert!(lookup(10, false).is_none());
+    }
+
+    #[test]
+    fn test_all_examples() {
+        for day in 1..=9 {
+            let solution = lookup(day, true).unwrap();
+            let example = solution.example();
+            let answers = solution.solve(example.input, &[1, 2]).unwrap();
+            assert_eq!(answers, vec![example.part1, example.part2], "day {} example", day);
+        }
+    }
+
+    #[test]
+    fn test_day1() {
+        let input = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+        let day = lookup(1, false).unwrap();
+        assert_eq!(day.solve(input, &[1, 2]).unwrap(), vec!["3", "6"]);
+    }
+
+    #[test]
+    fn test_day5() {
+        let input = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+        let day = lookup(5, false).unwrap();
+        assert_eq!(day.solve(input, &[1, 2]).unwrap(), vec!["3", "14"]);
+    }
+}
@@ -0,0 +1,283 @@
+use std::{error::Error, io::IsTerminal, path::PathBuf, process::ExitCode, time::Duration};
+use clap::{Parser, Subcommand};
+use common::InputSource;
+use tracing::{info_span, level_filters::LevelFilter};
+use tracing_subscriber::EnvFilter;
+
+mod batch;
+mod bench;
+mod days;
+mod output;
+mod verify;
+mod watch;
+
+use output::Format;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2025 solvers")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log what the solvers are doing to stderr: -v for each step's timings, -vv for debugging, -vvv for everything.
+    /// RUST_LOG, if set, takes precedence (e.g. RUST_LOG=day9=trace)
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// How many threads the solvers may use. 1 runs everything on a single thread, in order, for reproducible timings and
+    /// for debugging. Defaults to one per CPU, or RAYON_NUM_THREADS if that is set
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parse and solve a single day's puzzle
+    Run {
+        /// The day to run, 1-9
+        day: u8,
+        /// Only run this part of the puzzle (1 or 2). If not given, both parts are run
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Path to the puzzle input, or - to read it from stdin. Defaults to dayN/input.txt
+        #[arg(long)]
+        input: Option<String>,
+        /// Run the worked example from the puzzle text instead, and check the answers against it
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Keep running, re-solving whenever the input file changes and showing how the answers changed (text output only)
+        #[arg(long, conflicts_with = "example")]
+        watch: bool
+    },
+    /// Check each day's answers against a file of known-good answers, failing if any differ
+    Verify {
+        /// TOML file of expected answers, with a [dayN] table holding part1 and/or part2 for each day
+        #[arg(long, default_value = "expectations.toml")]
+        expectations: PathBuf,
+        /// Only check this day
+        #[arg(long)]
+        day: Option<u8>,
+        /// Check against each day's worked example rather than its input
+        #[arg(long)]
+        example: bool
+    },
+    /// Run a day against every input in a directory, or matching a glob, in parallel and tabulate the results
+    Batch {
+        /// The day to run, 1-9
+        day: u8,
+        /// A directory of inputs, or a glob pattern such as 'inputs/*.txt' (quoted, so the shell leaves it alone)
+        inputs: String,
+        /// Only run this part of the puzzle (1 or 2). If not given, both parts are run
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format
+    },
+    /// Time each phase of a day's solution over a number of runs
+    Bench {
+        /// The day to benchmark, 1-9
+        day: u8,
+        /// How many times to run each phase
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        /// Path to the puzzle input, or - to read it from stdin. Defaults to dayN/input.txt
+        #[arg(long)]
+        input: Option<String>,
+        /// Benchmark the worked example from the puzzle text instead
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+        /// Also run with rayon limited to a single thread, to see what the parallelism buys
+        #[arg(long)]
+        compare: bool
+    }
+}
+
+/**
+ * Sends log output to stderr, so that it never gets mixed into the answers (or the JSON) on stdout
+ */
+fn init_logging(verbose:u8) {
+    let level = match verbose {
+        0=>LevelFilter::WARN,
+        1=>LevelFilter::INFO,
+        2=>LevelFilter::DEBUG,
+        _=>LevelFilter::TRACE
+    };
+    let filter = match std::env::var("RUST_LOG") {
+        Ok(directives) if !directives.is_empty()=>EnvFilter::new(directives),
+        _=>EnvFilter::default().add_directive(level.into())
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .init();
+}
+
+/**
+ * Sizes rayon's global pool, which every day's parallel iterators run on
+ */
+fn init_threads(threads:Option<u16>) -> Result<(), Box<dyn Error>> {
+    if let Some(n) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(n as usize)
+            .build_global()
+            .map_err(|e| format!("could not start {} thread(s): {}", n, e))?;
+    }
+    Ok( () )
+}
+
+fn default_input(day_number:u8) -> InputSource {
+    InputSource::File(PathBuf::from(format!("day{}/input.txt", day_number)))
+}
+
+fn input_source(day_number:u8, input:Option<String>, example:bool) -> InputSource {
+    if example {
+        InputSource::Example
+    } else {
+        match input {
+            Some(arg)=>InputSource::from_arg(Some(&arg)),
+            None=>default_input(day_number)
+        }
+    }
+}
+
+fn run(day_number:u8, part:Option<u8>, source:InputSource, format:Format) -> Result<(), Box<dyn Error>> {
+    let _span = info_span!("day", day = day_number).entered();
+    let is_example = source==InputSource::Example;
+    let solution = days::lookup(day_number, is_example).ok_or_else(|| format!("there is no solver for day {}", day_number))?;
+    let example = solution.example();
+    let content = source.read(example.input)?;
+
+    let parts = match part {
+        Some(p)=>vec![p],
+        None=>vec![1, 2]
+    };
+    let report = solution.run(&content, &parts, format==Format::Json)?;
+    let expected = if is_example { Some(&example) } else { None };
+    match format {
+        Format::Text=>println!("{}", output::render_text(day_number, &report, expected)),
+        Format::Json=>println!("{}", output::render_json(day_number, &source, &report, expected))
+    }
+
+    let mismatches = match expected {
+        Some(e)=>report.answers.iter().filter(|a| a.text!=output::expected_answer(e, a.part)).count(),
+        None=>0
+    };
+    if mismatches > 0 {
+        Err(format!("{} answer(s) did not match the example", mismatches).into())
+    } else {
+        Ok( () )
+    }
+}
+
+/**
+ * Re-solves every time the input file changes, until interrupted
+ */
+fn run_watch(day_number:u8, part:Option<u8>, source:InputSource, format:Format) -> Result<(), Box<dyn Error>> {
+    let _span = info_span!("day", day = day_number).entered();
+    let path = match &source {
+        InputSource::File(path)=>path.clone(),
+        _=>return Err("--watch needs an input file to watch".into())
+    };
+    if format!=Format::Text {
+        return Err("--watch only supports text output".into())
+    }
+    let solution = days::lookup(day_number, false).ok_or_else(|| format!("there is no solver for day {}", day_number))?;
+    let parts = match part {
+        Some(p)=>vec![p],
+        None=>vec![1, 2]
+    };
+
+    let mut previous:Option<watch::RunState> = None;
+    watch::Watcher::new(&path).watch(Duration::from_millis(500), |content| {
+        if previous.is_some() {
+            println!("\n{} changed, re-solving", path.display());
+        }
+        let state = watch::RunState::from_result(&solution.run(content, &parts, false));
+        println!("{}", watch::describe_change(day_number, previous.as_ref(), &state));
+        previous = Some(state);
+    })
+}
+
+fn run_verify(expectations:PathBuf, day:Option<u8>, example:bool) -> Result<(), Box<dyn Error>> {
+    let text = std::fs::read_to_string(&expectations)
+        .map_err(|e| format!("could not read {}: {}", expectations.display(), e))?;
+    let mut expected = verify::parse_expectations(&text)?;
+    if let Some(day_number) = day {
+        expected.retain(|d, _| *d==day_number);
+        if expected.is_empty() {
+            return Err(format!("{} has no answers for day {}", expectations.display(), day_number).into())
+        }
+    }
+
+    let checks = verify::verify(&expected, |d| if example { InputSource::Example } else { default_input(d) });
+    for check in checks.iter() {
+        println!("{}", verify::describe(check));
+    }
+
+    let failures = checks.iter().filter(|c| c.outcome!=verify::Outcome::Pass).count();
+    if failures > 0 {
+        Err(format!("{} of {} answer(s) did not verify", failures, checks.len()).into())
+    } else {
+        Ok( () )
+    }
+}
+
+fn run_batch(day_number:u8, inputs:&str, part:Option<u8>, format:Format) -> Result<(), Box<dyn Error>> {
+    let _span = info_span!("day", day = day_number).entered();
+    let solution = days::lookup(day_number, false).ok_or_else(|| format!("there is no solver for day {}", day_number))?;
+    let paths = batch::find_inputs(inputs)?;
+    let parts = match part {
+        Some(p)=>vec![p],
+        None=>vec![1, 2]
+    };
+
+    let rows = batch::run_batch(solution.as_ref(), &paths, &parts);
+    match format {
+        Format::Text=>println!("{}", batch::render_table(&rows, &parts)),
+        Format::Json=>println!("{}", batch::render_json(day_number, &rows))
+    }
+
+    let failures = rows.iter().filter(|row| row.outcome.is_err()).count();
+    if failures > 0 {
+        Err(format!("{} of {} input(s) failed", failures, rows.len()).into())
+    } else {
+        Ok( () )
+    }
+}
+
+fn run_bench(day_number:u8, iterations:usize, source:InputSource, compare:bool) -> Result<(), Box<dyn Error>> {
+    let _span = info_span!("day", day = day_number).entered();
+    let solution = days::lookup(day_number, source==InputSource::Example).ok_or_else(|| format!("there is no solver for day {}", day_number))?;
+    let content = source.read(solution.example().input)?;
+
+    let parallel = bench::bench(solution.as_ref(), &content, iterations)?;
+    println!("{}", bench::render(&format!("Day {}, rayon with {} thread(s)", day_number, rayon::current_num_threads()), &parallel));
+    if compare {
+        let single = bench::bench_with_threads(solution.as_ref(), &content, iterations, 1)?;
+        println!("{}", bench::render(&format!("Day {}, single-threaded", day_number), &single));
+    }
+    Ok( () )
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    let result = init_threads(cli.threads).and_then(|_| match cli.command {
+        Command::Run { day, part, input, example, format, watch: true }=>run_watch(day, part, input_source(day, input, example), format),
+        Command::Run { day, part, input, example, format, watch: false }=>run(day, part, input_source(day, input, example), format),
+        Command::Verify { expectations, day, example }=>run_verify(expectations, day, example),
+        Command::Batch { day, inputs, part, format }=>run_batch(day, &inputs, part, format),
+        Command::Bench { day, iterations, input, example, compare }=>run_bench(day, iterations, input_source(day, input, example), compare)
+    });
+    //Print errors with Display rather than Debug, so that parse errors show where in the input they are
+    match result {
+        Ok( () )=>ExitCode::SUCCESS,
+        Err(e)=>{
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
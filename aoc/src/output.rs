@@ -0,0 +1,114 @@
+use std::time::Duration;
+use clap::ValueEnum;
+use common::{json, Example, InputSource, Report, Value};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable lines, one per answer
+    Text,
+    /// One JSON object per day, on a single line
+    Json
+}
+
+fn millis(d:&Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+pub fn describe_source(source:&InputSource) -> String {
+    match source {
+        InputSource::File(path)=>path.display().to_string(),
+        InputSource::Stdin=>"stdin".to_string(),
+        InputSource::Example=>"example".to_string()
+    }
+}
+
+/**
+ * Returns the answer the worked example expects for the given part
+ */
+pub fn expected_answer(example:&Example, part:u8) -> &'static str {
+    if part==1 {
+        example.part1
+    } else {
+        example.part2
+    }
+}
+
+pub fn render_text(day:u8, report:&Report, example:Option<&Example>) -> String {
+    let lines:Vec<String> = report.answers.iter().map(|answer| {
+        match example.map(|e| expected_answer(e, answer.part)) {
+            Some(expected) if expected==answer.text=>
+                format!("Day {} part {}: {} (matches the example)", day, answer.part, answer.text),
+            Some(expected)=>
+                format!("Day {} part {}: {} (the example expects {})", day, answer.part, answer.text, expected),
+            None=>format!("Day {} part {}: {}", day, answer.part, answer.text)
+        }
+    }).collect();
+    lines.join("\n")
+}
+
+pub fn render_json(day:u8, source:&InputSource, report:&Report, example:Option<&Example>) -> Value {
+    let mut answers = serde_json::Map::new();
+    let mut timings = serde_json::Map::new();
+    timings.insert("parse".to_string(), json!(millis(&report.parse_time)));
+
+    for answer in report.answers.iter() {
+        let mut entry = json!({ "answer": answer.value });
+        if let Some(e) = example {
+            let expected = expected_answer(e, answer.part);
+            entry["expected"] = json!(expected);
+            entry["matches"] = json!(expected==answer.text);
+        }
+        answers.insert(format!("part{}", answer.part), entry);
+        timings.insert(format!("part{}", answer.part), json!(millis(&answer.elapsed)));
+    }
+
+    json!({
+        "day": day,
+        "input": describe_source(source),
+        "answers": answers,
+        "timings_ms": timings,
+        "details": report.details
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::Answer;
+
+    fn report() -> Report {
+        Report {
+            parse_time: Duration::from_millis(2),
+            answers: vec![
+                Answer { part: 1, value: json!(3), text: "3".to_string(), elapsed: Duration::from_millis(1) },
+                Answer { part: 2, value: json!(7), text: "7".to_string(), elapsed: Duration::from_millis(5) },
+            ],
+            details: json!({ "movements": 10 })
+        }
+    }
+
+    #[test]
+    fn test_render_text() {
+        let example = Example { input: "", part1: "3", part2: "6" };
+        assert_eq!(render_text(1, &report(), None), "Day 1 part 1: 3\nDay 1 part 2: 7");
+        assert_eq!(render_text(1, &report(), Some(&example)),
+            "Day 1 part 1: 3 (matches the example)\nDay 1 part 2: 7 (the example expects 6)");
+    }
+
+    #[test]
+    fn test_render_json() {
+        let rendered = render_json(1, &InputSource::Stdin, &report(), None);
+        assert_eq!(rendered["day"], json!(1));
+        assert_eq!(rendered["input"], json!("stdin"));
+        assert_eq!(rendered["answers"]["part2"]["answer"], json!(7));
+        assert_eq!(rendered["timings_ms"]["part2"], json!(5.0));
+        assert_eq!(rendered["details"]["movements"], json!(10));
+        assert!(rendered["answers"]["part1"].get("expected").is_none());
+
+        let example = Example { input: "", part1: "3", part2: "6" };
+        let rendered = render_json(1, &InputSource::Example, &report(), Some(&example));
+        assert_eq!(rendered["answers"]["part1"]["matches"], json!(true));
+        assert_eq!(rendered["answers"]["part2"]["matches"], json!(false));
+        assert_eq!(rendered["answers"]["part2"]["expected"], json!("6"));
+    }
+}
@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, error::Error};
+use common::InputSource;
+use tracing::info_span;
+use crate::days;
+
+/**
+ * The answers we expect for one day, as read from the expectations file.  Either part may be missing,
+ * in which case it isn't checked
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Expectation {
+    pub part1: Option<String>,
+    pub part2: Option<String>
+}
+
+impl Expectation {
+    fn parts(&self) -> Vec<(u8, &str)> {
+        [(1, &self.part1), (2, &self.part2)].into_iter()
+            .filter_map(|(part, answer)| answer.as_deref().map(|a| (part, a)))
+            .collect()
+    }
+}
+
+/**
+ * The result of checking one part of one day
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    Failed(String)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome
+}
+
+fn answer_to_string(value:&toml::Value) -> Result<String, Box<dyn Error>> {
+    match value {
+        toml::Value::String(s)=>Ok(s.clone()),
+        toml::Value::Integer(n)=>Ok(n.to_string()),
+        other=>Err(format!("answers must be strings or integers, not {}", other.type_str()).into())
+    }
+}
+
+/**
+ * Parses an expectations file.  Each day gets its own table, keyed `dayN`, holding `part1` and/or `part2`:
+ *
+ * ```toml
+ * [day1]
+ * part1 = 1034
+ * part2 = "6166"
+ * ```
+ */
+pub fn parse_expectations(text:&str) -> Result<BTreeMap<u8, Expectation>, Box<dyn Error>> {
+    let table = text.parse::<toml::Table>()?;
+    let mut expectations = BTreeMap::new();
+
+    for (key, value) in table.iter() {
+        let day = key.strip_prefix("day")
+            .and_then(|n| n.parse::<u8>().ok())
+            .ok_or_else(|| format!("expected a key like day1, got {}", key))?;
+        let parts = value.as_table().ok_or_else(|| format!("{} should be a table of answers", key))?;
+
+        let mut expectation = Expectation::default();
+        for (part, answer) in parts.iter() {
+            let answer = answer_to_string(answer).map_err(|e| format!("{}.{}: {}", key, part, e))?;
+            match part.as_str() {
+                "part1"=>expectation.part1 = Some(answer),
+                "part2"=>expectation.part2 = Some(answer),
+                _=>return Err(format!("{}.{}: only part1 and part2 are allowed", key, part).into())
+            }
+        }
+        expectations.insert(day, expectation);
+    }
+    Ok(expectations)
+}
+
+fn check_day(day:u8, expectation:&Expectation, source:&InputSource) -> Vec<Check> {
+    let _span = info_span!("day", day).entered();
+    let parts = expectation.parts();
+    let part_numbers:Vec<u8> = parts.iter().map(|(part, _)| *part).collect();
+
+    let answers = days::lookup(day, *source==InputSource::Example)
+        .ok_or_else(|| format!("there is no solver for day {}", day).into())
+        .and_then(|solution| {
+            let content = source.read(solution.example().input)?;
+            solution.solve(&content, &part_numbers)
+        });
+
+    match answers {
+        Ok(answers)=>parts.iter().zip(answers).map(|((part, expected), actual)| {
+            let outcome = if *expected==actual {
+                Outcome::Pass
+            } else {
+                Outcome::Mismatch { expected: expected.to_string(), actual }
+            };
+            Check { day, part: *part, outcome }
+        }).collect(),
+        //If we couldn't get any answers then every part we were asked to check has failed
+        Err(e)=>part_numbers.iter().map(|part| {
+            Check { day, part: *part, outcome: Outcome::Failed(e.to_string()) }
+        }).collect()
+    }
+}
+
+/**
+ * Runs every day that has expectations against the input given by `source_for`, and checks each answer
+ */
+pub fn verify(expectations:&BTreeMap<u8, Expectation>, source_for:impl Fn(u8) -> InputSource) -> Vec<Check> {
+    expectations.iter()
+        .flat_map(|(day, expectation)| check_day(*day, expectation, &source_for(*day)))
+        .collect()
+}
+
+pub fn describe(check:&Check) -> String {
+    match &check.outcome {
+        Outcome::Pass=>format!("Day {} part {}: ok", check.day, check.part),
+        Outcome::Mismatch { expected, actual }=>
+            format!("Day {} part {}: MISMATCH expected {} but got {}", check.day, check.part, expected, actual),
+        Outcome::Failed(e)=>format!("Day {} part {}: FAILED {}", check.day, check.part, e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_expectations() {
+        let expectations = parse_expectations("[day1]
+part1 = 3
+part2 = \"6\"
+
+[day5]
+part2 = 14
+").unwrap();
+        assert_eq!(expectations.len(), 2);
+        assert_eq!(expectations[&1], Expectation { part1: Some("3".to_string()), part2: Some("6".to_string()) });
+        assert_eq!(expectations[&5], Expectation { part1: None, part2: Some("14".to_string()) });
+
+        assert!(parse_expectations("[dayone]\npart1 = 3").is_err());
+        assert!(parse_expectations("[day1]\npart3 = 3").is_err());
+        assert!(parse_expectations("[day1]\npart1 = 3.5").is_err());
+        assert!(parse_expectations("day1 = 3").is_err());
+    }
+
+    #[test]
+    fn test_verify_examples() {
+        let expectations = parse_expectations("[day1]
+part1 = 3
+part2 = 7
+
+[day5]
+part2 = 14
+
+[day42]
+part1 = 1
+").unwrap();
+        let checks = verify(&expectations, |_| InputSource::Example);
+        let outcomes:Vec<(u8, u8, &Outcome)> = checks.iter().map(|c| (c.day, c.part, &c.outcome)).collect();
+        assert_eq!(outcomes, vec![
+            (1, 1, &Outcome::Pass),
+            (1, 2, &Outcome::Mismatch { expected: "7".to_string(), actual: "6".to_string() }),
+            (5, 2, &Outcome::Pass),
+            (42, 1, &Outcome::Failed("there is no solver for day 42".to_string())),
+        ]);
+        assert_eq!(describe(&checks[1]), "Day 1 part 2: MISMATCH expected 7 but got 6");
+    }
+}
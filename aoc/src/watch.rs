@@ -0,0 +1,134 @@
+use std::{error::Error, path::{Path, PathBuf}, thread, time::Duration};
+use common::{debug, Report};
+
+/**
+ * The outcome of one run in watch mode, kept so that the next run can be compared against it
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunState {
+    Answers(Vec<(u8, String)>),
+    Failed(String)
+}
+
+impl RunState {
+    pub fn from_result(result:&Result<Report, Box<dyn Error>>) -> RunState {
+        match result {
+            Ok(report)=>RunState::Answers(report.answers.iter().map(|a| (a.part, a.text.clone())).collect()),
+            Err(e)=>RunState::Failed(e.to_string())
+        }
+    }
+}
+
+/**
+ * Describes the latest run, noting for each answer whether it changed since the previous run
+ */
+pub fn describe_change(day:u8, previous:Option<&RunState>, current:&RunState) -> String {
+    match current {
+        RunState::Failed(e)=>format!("Day {} failed: {}", day, e),
+        RunState::Answers(answers)=>{
+            let lines:Vec<String> = answers.iter().map(|(part, text)| {
+                let before = match previous {
+                    Some(RunState::Answers(old))=>old.iter().find(|(p, _)| p==part).map(|(_, t)| t.as_str()),
+                    _=>None
+                };
+                match (previous, before) {
+                    (None, _)=>format!("Day {} part {}: {}", day, part, text),
+                    (Some(RunState::Failed(_)), _)=>format!("Day {} part {}: {} (previously failed)", day, part, text),
+                    (Some(_), Some(old)) if old==text=>format!("Day {} part {}: {} (unchanged)", day, part, text),
+                    (Some(_), Some(old))=>format!("Day {} part {}: {} (was {})", day, part, text, old),
+                    (Some(_), None)=>format!("Day {} part {}: {} (new)", day, part, text)
+                }
+            }).collect();
+            lines.join("\n")
+        }
+    }
+}
+
+/**
+ * Keeps an eye on a file by polling it, reporting its content whenever it differs from last time.
+ * Comparing the content rather than the modification time means that saving without changes doesn't cause a re-run
+ */
+pub struct Watcher {
+    path: PathBuf,
+    last: Option<String>
+}
+
+impl Watcher {
+    pub fn new(path:&Path) -> Watcher {
+        Watcher { path: path.to_path_buf(), last: None }
+    }
+
+    /**
+     * Returns the file's content if it has changed since the last poll (the first poll always counts as a change).
+     * A file that can't be read is treated as unchanged, as editors often remove and re-create a file when saving it
+     */
+    pub fn poll(&mut self) -> Option<String> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(content) if self.last.as_ref()!=Some(&content)=>{
+                self.last = Some(content.clone());
+                Some(content)
+            },
+            Ok(_)=>None,
+            Err(e)=>{
+                debug!(path = %self.path.display(), error = %e, "could not read the watched file");
+                None
+            }
+        }
+    }
+
+    /**
+     * Polls the file every `interval`, calling `on_change` with the new content each time it changes.  Only returns if
+     * the file can't be read to begin with
+     */
+    pub fn watch(&mut self, interval:Duration, mut on_change:impl FnMut(&str)) -> Result<(), Box<dyn Error>> {
+        let first = std::fs::read_to_string(&self.path).map_err(|e| format!("could not watch {}: {}", self.path.display(), e))?;
+        self.last = Some(first.clone());
+        on_change(&first);
+        loop {
+            thread::sleep(interval);
+            if let Some(content) = self.poll() {
+                on_change(&content);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn answers(a:&[(u8, &str)]) -> RunState {
+        RunState::Answers(a.iter().map(|(p, t)| (*p, t.to_string())).collect())
+    }
+
+    #[test]
+    fn test_describe_change() {
+        let first = answers(&[(1, "3"), (2, "6")]);
+        assert_eq!(describe_change(1, None, &first), "Day 1 part 1: 3\nDay 1 part 2: 6");
+
+        let second = answers(&[(1, "3"), (2, "7")]);
+        assert_eq!(describe_change(1, Some(&first), &second), "Day 1 part 1: 3 (unchanged)\nDay 1 part 2: 7 (was 6)");
+
+        let failed = RunState::Failed("line 2, column 1: bad".to_string());
+        assert_eq!(describe_change(1, Some(&second), &failed), "Day 1 failed: line 2, column 1: bad");
+        assert_eq!(describe_change(1, Some(&failed), &second), "Day 1 part 1: 3 (previously failed)\nDay 1 part 2: 7 (previously failed)");
+        assert_eq!(describe_change(1, Some(&answers(&[(1, "3")])), &second), "Day 1 part 1: 3 (unchanged)\nDay 1 part 2: 7 (new)");
+    }
+
+    #[test]
+    fn test_poll() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-test-{}.txt", std::process::id()));
+        std::fs::write(&path, "L68\n").unwrap();
+
+        let mut watcher = Watcher::new(&path);
+        assert_eq!(watcher.poll(), Some("L68\n".to_string()));
+        assert_eq!(watcher.poll(), None);
+        std::fs::write(&path, "L68\n").unwrap();
+        assert_eq!(watcher.poll(), None);
+        std::fs::write(&path, "R10\n").unwrap();
+        assert_eq!(watcher.poll(), Some("R10\n".to_string()));
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.poll(), None);
+    }
+}
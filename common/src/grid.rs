@@ -0,0 +1,274 @@
+use std::{error::Error, fmt::Display};
+use crate::{numbered_lines, ParseError};
+
+/**
+ * Which of the surrounding cells count as neighbours
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /** Up, down, left and right */
+    Four,
+    /** As Four, plus the diagonals */
+    Eight
+}
+
+impl Neighbourhood {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Four=>&[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighbourhood::Eight=>&[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)]
+        }
+    }
+}
+
+/**
+ * A rectangular 2d grid of cells, addressed by (row, column) from the top-left.
+ * Cells are stored row by row, and every row is the same width
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    /**
+     * Builds a grid from its cells, given row by row.  The number of cells must be a multiple of the width
+     */
+    pub fn from_cells(width:usize, cells:Vec<T>) -> Result<Grid<T>, Box<dyn Error>> {
+        if width==0 {
+            return if cells.is_empty() {
+                Ok(Grid { width: 0, height: 0, cells })
+            } else {
+                Err("a grid with cells in it must have a width".into())
+            }
+        }
+        if !cells.len().is_multiple_of(width) {
+            return Err(format!("{} cells cannot be split into rows of {}", cells.len(), width).into())
+        }
+        Ok(Grid { width, height: cells.len() / width, cells })
+    }
+
+    /**
+     * Builds a grid from a list of rows, which must all be the same length
+     */
+    pub fn from_rows(rows:Vec<Vec<T>>) -> Result<Grid<T>, Box<dyn Error>> {
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        if let Some(i) = rows.iter().position(|row| row.len()!=width) {
+            return Err(format!("row {} has {} cells but the first row has {}", i, rows[i].len(), width).into())
+        }
+        Grid::from_cells(width, rows.into_iter().flatten().collect())
+    }
+
+    /**
+     * Parses a grid from a character map, one row per line, converting each character with `cell`.
+     * Blank lines are skipped, and every other line must be the same width as the first
+     */
+    pub fn parse<E: Display>(input:&str, cell:impl Fn(char) -> Result<T, E>) -> Result<Grid<T>, ParseError> {
+        let mut width:Option<usize> = None;
+        let mut cells:Vec<T> = vec![];
+
+        for (line_number, line) in numbered_lines(input).filter(|(_, line)| !line.trim().is_empty()) {
+            let line_width = line.chars().count();
+            match width {
+                Some(w) if w!=line_width=>return Err(
+                    ParseError::whole_line(line, format!("expected {} cells to match the first line, found {}", w, line_width)).on_line(line_number)
+                ),
+                _=>width = Some(line_width)
+            }
+            for (col, ch) in line.char_indices() {
+                let value = cell(ch).map_err(|e| ParseError::at(line, &line[col..col+ch.len_utf8()], e.to_string()).on_line(line_number))?;
+                cells.push(value);
+            }
+        }
+        let width = width.unwrap_or(0);
+        Ok(Grid { width, height: cells.len().checked_div(width).unwrap_or(0), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row:usize, col:usize) -> Option<&T> {
+        if row < self.height && col < self.width {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row:usize, col:usize) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            self.cells.get_mut(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Replaces the cell at (row, col), returning an error if it is outside the grid
+     */
+    pub fn set(&mut self, row:usize, col:usize, value:T) -> Result<(), Box<dyn Error>> {
+        match self.get_mut(row, col) {
+            Some(cell)=>{
+                *cell = value;
+                Ok( () )
+            },
+            None=>Err(format!("({}, {}) is outside the {}x{} grid", row, col, self.width, self.height).into())
+        }
+    }
+
+    /**
+     * Like get, but takes signed co-ordinates so that callers can step off the edge without checking first
+     */
+    pub fn get_signed(&self, row:isize, col:isize) -> Option<&T> {
+        if row < 0 || col < 0 {
+            None
+        } else {
+            self.get(row as usize, col as usize)
+        }
+    }
+
+    pub fn row(&self, row:usize) -> Option<&[T]> {
+        if row < self.height {
+            Some(&self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        //chunks panics on a zero size, and a zero-width grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col:usize) -> impl Iterator<Item = &T> {
+        let cells = if col < self.width { &self.cells[col..] } else { &self.cells[0..0] };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /**
+     * All of the cells, row by row
+     */
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /**
+     * Every cell along with its (row, col) position, row by row
+     */
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width.max(1);
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    /**
+     * The positions of the cells around (row, col) which are inside the grid
+     */
+    pub fn neighbours(&self, row:usize, col:usize, neighbourhood:Neighbourhood) -> impl Iterator<Item = (usize, usize)> + '_ {
+        neighbourhood.offsets().iter().filter_map(move |(dr, dc)| {
+            let r = row.checked_add_signed(*dr)?;
+            let c = col.checked_add_signed(*dc)?;
+            self.get(r, c).map(|_| (r, c))
+        })
+    }
+
+    /**
+     * The cells around (row, col) which are inside the grid
+     */
+    pub fn neighbour_cells(&self, row:usize, col:usize, neighbourhood:Neighbourhood) -> impl Iterator<Item = &T> + '_ {
+        self.neighbours(row, col, neighbourhood).filter_map(|(r, c)| self.get(r, c))
+    }
+
+    /**
+     * Builds a new grid of the same shape by converting every cell
+     */
+    pub fn map<U>(&self, f:impl Fn(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /**
+     * Swaps rows for columns
+     */
+    pub fn transpose(&self) -> Grid<T> where T: Clone {
+        let cells = (0..self.width).flat_map(|col| self.column(col).cloned()).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /**
+     * Draws the grid as lines of text, one character per cell
+     */
+    pub fn render(&self, to_char:impl Fn(&T) -> char) -> String {
+        let lines:Vec<String> = self.rows().map(|row| row.iter().map(&to_char).collect()).collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digits(input:&str) -> Grid<u32> {
+        Grid::parse(input, |ch| ch.to_digit(10).ok_or(format!("'{}' is not a digit", ch))).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456\n\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 0), Some(&4));
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.render(|n| char::from_digit(*n, 10).unwrap()), "123\n456");
+
+        let e = Grid::parse("123\n4x6", |ch| ch.to_digit(10).ok_or(format!("'{}' is not a digit", ch))).unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 2, "'x' is not a digit"));
+        let e = Grid::parse("123\n45", |ch| ch.to_digit(10).ok_or("not a digit")).unwrap_err();
+        assert_eq!(e.line, 2);
+
+        let empty = digits("");
+        assert_eq!((empty.width(), empty.height(), empty.rows().count()), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), vec![2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &5)));
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.render(|n| char::from_digit(*n, 10).unwrap()), "14\n25\n36");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789");
+        let mut around_corner:Vec<u32> = grid.neighbour_cells(0, 0, Neighbourhood::Eight).copied().collect();
+        around_corner.sort();
+        assert_eq!(around_corner, vec![2, 4, 5]);
+        assert_eq!(grid.neighbours(1, 1, Neighbourhood::Eight).count(), 8);
+        assert_eq!(grid.neighbours(1, 1, Neighbourhood::Four).collect::<Vec<(usize, usize)>>(), vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn test_construction() {
+        let mut grid = Grid::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']]).unwrap();
+        grid.set(1, 0, 'x').unwrap();
+        assert!(grid.set(2, 0, 'x').is_err());
+        assert_eq!(grid.map(|ch| ch.to_ascii_uppercase()).render(|ch| *ch), "AB\nXD");
+
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+        assert!(Grid::from_cells(2, vec![1, 2, 3]).is_err());
+        assert_eq!(Grid::from_cells(2, vec![1, 2, 3, 4]).unwrap().height(), 2);
+    }
+}
@@ -0,0 +1,84 @@
+use std::{env, error::Error, fs::File, io::{self, Read}, path::{Path, PathBuf}};
+
+/**
+ * Where a puzzle's input should come from
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Example
+}
+
+impl InputSource {
+    /**
+     * Interprets a command-line argument: `-` means stdin, `--example` means the puzzle's worked example and
+     * anything else is a path.  If there is no argument we look for `input.txt` in the current directory
+     */
+    pub fn from_arg(arg:Option<&str>) -> InputSource {
+        match arg {
+            None=>InputSource::File(PathBuf::from("input.txt")),
+            Some("-")=>InputSource::Stdin,
+            Some("--example")=>InputSource::Example,
+            Some(path)=>InputSource::File(PathBuf::from(path))
+        }
+    }
+
+    /**
+     * Reads the first command-line argument as an input source
+     */
+    pub fn from_args() -> InputSource {
+        Self::from_arg(env::args().nth(1).as_deref())
+    }
+
+    /**
+     * Returns the input text.  The example text has to be supplied by the caller, as it belongs to the puzzle
+     */
+    pub fn read(&self, example:&str) -> Result<String, Box<dyn Error>> {
+        match self {
+            InputSource::File(path)=>read_input(path),
+            InputSource::Stdin=>{
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                Ok(content)
+            },
+            InputSource::Example=>Ok(example.to_string())
+        }
+    }
+}
+
+/**
+ * Reads the whole of the given puzzle input file into a string
+ */
+pub fn read_input(path:&Path) -> Result<String, Box<dyn Error>> {
+    let mut f = File::open(path).map_err(|e| format!("could not open {}: {}", path.display(), e))?;
+    let mut content = String::new();
+    f.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/**
+ * Reads the puzzle input selected by the first command-line argument (see `InputSource::from_arg`)
+ */
+pub fn read_input_from_args(example:&str) -> Result<String, Box<dyn Error>> {
+    InputSource::from_args().read(example)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg(None), InputSource::File(PathBuf::from("input.txt")));
+        assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+        assert_eq!(InputSource::from_arg(Some("--example")), InputSource::Example);
+        assert_eq!(InputSource::from_arg(Some("day5/input.txt")), InputSource::File(PathBuf::from("day5/input.txt")));
+    }
+
+    #[test]
+    fn test_read_example() {
+        assert_eq!(InputSource::Example.read("1,2,3").unwrap(), "1,2,3");
+        assert!(InputSource::File(PathBuf::from("/this/does/not/exist")).read("").is_err());
+    }
+}
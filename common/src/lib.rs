@@ -0,0 +1,176 @@
+use std::{error::Error, fmt::Display, time::{Duration, Instant}};
+
+mod grid;
+mod input;
+mod parse_error;
+mod snapshot;
+pub use grid::{Grid, Neighbourhood};
+pub use input::{read_input, read_input_from_args, InputSource};
+pub use parse_error::{numbered_lines, ParseError};
+pub use snapshot::{blessing, check_snapshot, diff_lines, BLESS_VAR};
+pub use serde_json::{json, Value};
+pub use tracing::{debug, info, trace, warn};
+use tracing::info_span;
+
+/**
+ * The worked example given in the puzzle text, along with the answers the puzzle text gives for it
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: &'static str,
+    pub part2: &'static str
+}
+
+/**
+ * The shared shape of every day's solution: parse the raw puzzle text once, then answer each part from the
+ * parsed input.  Tooling (runners, benchmarks, verifiers) is written against this trait rather than against
+ * each day's own entry points
+ */
+pub trait Puzzle {
+    type Input;
+    type Output: Display + Into<Value>;
+
+    fn parse(&self, input:&str) -> Result<Self::Input, Box<dyn Error>>;
+    fn part1(&self, input:&Self::Input) -> Result<Self::Output, Box<dyn Error>>;
+    fn part2(&self, input:&Self::Input) -> Result<Self::Output, Box<dyn Error>>;
+    fn example(&self) -> Example;
+
+    /**
+     * Day-specific facts about the input and the solution that are worth reporting alongside the answers,
+     * e.g. how many items were parsed or the shape of the winning solution.  Only called when they are asked for
+     */
+    fn details(&self, _input:&Self::Input) -> Result<Value, Box<dyn Error>> {
+        Ok(Value::Null)
+    }
+}
+
+/**
+ * The answer to one part of a puzzle and how long it took to get it
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub part: u8,
+    pub value: Value,
+    pub text: String,
+    pub elapsed: Duration
+}
+
+/**
+ * Everything learned from one run of a puzzle over an input
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+    pub details: Value
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/**
+ * Type-erased view of a Puzzle, so that puzzles with different Input and Output types can be stored together
+ * (e.g. in a table of all the days), and shared between threads.  Every Puzzle gets this for free
+ */
+pub trait Solution: Send + Sync {
+    /**
+     * Parses the input and answers each of the requested parts in the order asked for, timing each step.
+     * The day-specific details are only gathered if `with_details` is set
+     */
+    fn run(&self, input:&str, parts:&[u8], with_details:bool) -> Result<Report, Box<dyn Error>>;
+
+    fn example(&self) -> Example;
+
+    /**
+     * Parses the input and returns just the answer to each of the requested parts, in the order asked for
+     */
+    fn solve(&self, input:&str, parts:&[u8]) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(self.run(input, parts, false)?.answers.into_iter().map(|a| a.text).collect())
+    }
+}
+
+impl<P: Puzzle + Send + Sync> Solution for P {
+    fn run(&self, input:&str, parts:&[u8], with_details:bool) -> Result<Report, Box<dyn Error>> {
+        let (parsed, parse_time) = info_span!("parse", bytes = input.len()).in_scope(|| timed(|| self.parse(input)));
+        let parsed = parsed.inspect_err(|e| debug!(error = %e, "parse failed"))?;
+        debug!(elapsed = ?parse_time, "parsed input");
+
+        let answers = parts.iter().map(|part| {
+            let _span = info_span!("solve", part = *part).entered();
+            let (result, elapsed) = match part {
+                1=>timed(|| self.part1(&parsed)),
+                2=>timed(|| self.part2(&parsed)),
+                _=>return Err(format!("there is no part {}", part).into())
+            };
+            let output = result.inspect_err(|e| debug!(error = %e, "solve failed"))?;
+            info!(answer = %output, elapsed = ?elapsed, "solved");
+            Ok(Answer { part: *part, text: output.to_string(), value: output.into(), elapsed })
+        }).collect::<Result<Vec<Answer>, Box<dyn Error>>>()?;
+
+        let details = if with_details {
+            self.details(&parsed)?
+        } else {
+            Value::Null
+        };
+        Ok(Report { parse_time, answers, details })
+    }
+
+    fn example(&self) -> Example {
+        Puzzle::example(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Summer;
+
+    impl Puzzle for Summer {
+        type Input = Vec<u32>;
+        type Output = u32;
+
+        fn parse(&self, input:&str) -> Result<Vec<u32>, Box<dyn Error>> {
+            input.split(",").map(|n| n.parse::<u32>().map_err(|e| e.into())).collect()
+        }
+
+        fn part1(&self, input:&Vec<u32>) -> Result<u32, Box<dyn Error>> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(&self, input:&Vec<u32>) -> Result<u32, Box<dyn Error>> {
+            input.iter().max().copied().ok_or_else(|| "no numbers".into())
+        }
+
+        fn example(&self) -> Example {
+            Example { input: "1,5,3", part1: "9", part2: "5" }
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution:&dyn Solution = &Summer;
+        assert_eq!(solution.solve("1,5,3", &[1, 2]).unwrap(), vec!["9", "5"]);
+        assert_eq!(solution.solve("1,5,3", &[2]).unwrap(), vec!["5"]);
+        assert!(solution.solve("1,5,3", &[3]).is_err());
+        assert!(solution.solve("1,x,3", &[1]).is_err());
+
+        let example = solution.example();
+        assert_eq!(solution.solve(example.input, &[1, 2]).unwrap(), vec![example.part1, example.part2]);
+    }
+
+    #[test]
+    fn test_run() {
+        let report = Summer.run("1,5,3", &[2, 1], true).unwrap();
+        assert_eq!(report.answers.len(), 2);
+        assert_eq!(report.answers[0].part, 2);
+        assert_eq!(report.answers[0].value, json!(5));
+        assert_eq!(report.answers[1].part, 1);
+        assert_eq!(report.answers[1].text, "9");
+        assert_eq!(report.details, Value::Null);
+    }
+}
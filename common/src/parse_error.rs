@@ -0,0 +1,118 @@
+use std::{error::Error, fmt::Display};
+
+/**
+ * A problem found while parsing puzzle input, pinned to where in the input it was found.
+ * Lines and columns count from 1, and columns count characters rather than bytes
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /** How many characters the problem spans, so we can underline all of it */
+    pub width: usize,
+    /** The whole line the problem was found on */
+    pub snippet: String,
+    pub message: String
+}
+
+/**
+ * Works out where `part` starts within `line`, as a character count.  This only makes sense if `part` was sliced
+ * out of `line` (e.g. by split or a regex), which is how the parsers get hold of the bits they complain about
+ */
+fn column_of(line:&str, part:&str) -> Option<usize> {
+    let line_start = line.as_ptr() as usize;
+    let part_start = part.as_ptr() as usize;
+    if part_start < line_start || part_start + part.len() > line_start + line.len() {
+        return None
+    }
+    let offset = part_start - line_start;
+    line.get(..offset).map(|before| before.chars().count() + 1)
+}
+
+impl ParseError {
+    pub fn new(message:impl Into<String>, line:usize, column:usize, snippet:&str) -> ParseError {
+        ParseError { line, column, width: 1, snippet: snippet.to_string(), message: message.into() }
+    }
+
+    /**
+     * An error about `part`, which is a slice of `line`.  If it isn't, the whole line is blamed.
+     * The line number is 1 until it is set with `on_line`, as most parsers work on a line at a time
+     */
+    pub fn at(line:&str, part:&str, message:impl Into<String>) -> ParseError {
+        match column_of(line, part) {
+            Some(column)=>ParseError {
+                width: part.chars().count().max(1),
+                ..ParseError::new(message, 1, column, line)
+            },
+            None=>ParseError::whole_line(line, message)
+        }
+    }
+
+    /**
+     * An error about a line as a whole, e.g. one that is the wrong length
+     */
+    pub fn whole_line(line:&str, message:impl Into<String>) -> ParseError {
+        ParseError { width: line.chars().count().max(1), ..ParseError::new(message, 1, 1, line) }
+    }
+
+    /**
+     * Sets the line number, for when the error came from a parser that was only given the one line
+     */
+    pub fn on_line(self, line:usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "  {}", self.snippet)?;
+        write!(f, "  {}{}", " ".repeat(self.column-1), "^".repeat(self.width))
+    }
+}
+
+impl Error for ParseError { }
+
+/**
+ * Iterates over the lines of some input along with their line numbers, counting from 1
+ */
+pub fn numbered_lines(input:&str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(i, line)| (i+1, line))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let line = "12,x4,7";
+        let part = line.split(",").nth(1).unwrap();
+        let e = ParseError::at(line, part, "not a number").on_line(3);
+        assert_eq!((e.line, e.column, e.width), (3, 4, 2));
+        assert_eq!(e.to_string(), "line 3, column 4: not a number
+  12,x4,7
+     ^^");
+
+        //Something that didn't come from the line gets the whole line blamed
+        let e = ParseError::at(line, "x4", "not a number");
+        assert_eq!((e.line, e.column, e.width), (1, 1, 7));
+    }
+
+    #[test]
+    fn test_columns_count_chars() {
+        let line = "é→x";
+        let part = &line[5..];
+        let e = ParseError::at(line, part, "bad");
+        assert_eq!(e.column, 3);
+        assert_eq!(e.to_string(), "line 1, column 3: bad
+  é→x
+    ^");
+    }
+
+    #[test]
+    fn test_numbered_lines() {
+        let lines:Vec<(usize, &str)> = numbered_lines("a\nb\n").collect();
+        assert_eq!(lines, vec![(1, "a"), (2, "b")]);
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::Path;
+
+/**
+ * The environment variable which, when set to anything other than 0, makes snapshot checks write what they were
+ * given as the new expected output instead of comparing against it
+ */
+pub const BLESS_VAR: &str = "BLESS_SNAPSHOTS";
+
+pub fn blessing() -> bool {
+    std::env::var(BLESS_VAR).map(|v| !v.is_empty() && v!="0").unwrap_or(false)
+}
+
+/**
+ * Compares `actual` with the snapshot stored at `path`, returning a diff of the two if they differ.  When `bless` is
+ * set the snapshot is (re)written from `actual` instead.  Snapshot files end with a newline, which isn't part of the
+ * snapshot, so that they play nicely with editors
+ */
+pub fn check_snapshot(path:&Path, actual:&str, bless:bool) -> Result<(), String> {
+    if bless {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+        }
+        return std::fs::write(path, format!("{}\n", actual)).map_err(|e| format!("could not write {}: {}", path.display(), e))
+    }
+
+    let expected = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read the snapshot {}: {}\nRun the tests with {}=1 to create it", path.display(), e, BLESS_VAR))?;
+    let expected = expected.strip_suffix('\n').unwrap_or(&expected);
+    if expected==actual {
+        Ok( () )
+    } else {
+        Err(format!("{} does not match (- expected, + actual):\n{}\nIf the new output is right, run the tests with {}=1 to update it",
+            path.display(), diff_lines(expected, actual), BLESS_VAR))
+    }
+}
+
+/**
+ * A line by line diff of two texts, marking lines only in `expected` with - and lines only in `actual` with +.
+ * Snapshots are small, so the plain longest-common-subsequence table is plenty fast enough
+ */
+pub fn diff_lines(expected:&str, actual:&str) -> String {
+    let old:Vec<&str> = expected.lines().collect();
+    let new:Vec<&str> = actual.lines().collect();
+
+    //common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0_usize; new.len()+1]; old.len()+1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i]==new[j] {
+                common[i+1][j+1] + 1
+            } else {
+                common[i+1][j].max(common[i][j+1])
+            };
+        }
+    }
+
+    let mut lines:Vec<String> = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i]==new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j==new.len() || common[i+1][j] >= common[i][j+1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+/**
+ * Asserts that a rendered output matches the snapshot `snapshots/<name>.txt` in the calling crate, printing a diff if it
+ * doesn't.  Run the tests with BLESS_SNAPSHOTS=1 to write the snapshots from the current output
+ */
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {{
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(format!("{}.txt", $name));
+        if let Err(e) = $crate::check_snapshot(&path, &$actual, $crate::blessing()) {
+            panic!("{}", e)
+        }
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(diff_lines("a\nb\nc", "a\nb\nc"), "  a\n  b\n  c");
+        assert_eq!(diff_lines("..#\n.#.\n#..", "..#\n.x.\n#.."), "  ..#\n- .#.\n+ .x.\n  #..");
+        assert_eq!(diff_lines("a\nb", "a\nb\nc"), "  a\n  b\n+ c");
+        assert_eq!(diff_lines("a\nb\nc", "b"), "- a\n  b\n- c");
+    }
+
+    #[test]
+    fn test_check_snapshot() {
+        let path = std::env::temp_dir().join(format!("common-snapshot-test-{}", std::process::id())).join("grid.txt");
+        let e = check_snapshot(&path, "ab\ncd", false).unwrap_err();
+        assert!(e.contains(BLESS_VAR));
+
+        check_snapshot(&path, "ab\ncd", true).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "ab\ncd\n");
+        check_snapshot(&path, "ab\ncd", false).unwrap();
+
+        let e = check_snapshot(&path, "ab\nce", false).unwrap_err();
+        assert!(e.contains("  ab\n- cd\n+ ce\n"));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
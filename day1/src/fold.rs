@@ -0,0 +1,233 @@
+use std::error::Error;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use crate::{clicks_on, history::DialState, DialMode, Movement, SafeDial};
+
+/**
+ * The zero counts a segment adds for every starting position from `from` up to the next piece
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Piece {
+    from: u64,
+    zero_counter: u64,
+    zero_click_counter: u64
+}
+
+/**
+ * What a run of movements does to a wrapping or detented dial, whatever position it starts from: it always moves
+ * the dial the same offset around, and the zero counts it adds only change at a few starting positions, so they are
+ * kept as pieces between those.  Joining two segments with `then` is associative, so a long log can be summarised
+ * in parts, on as many threads as there are, and the parts joined in order
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    size: u64,
+    /** How far to the right the segment moves the dial, less any whole turns */
+    offset: u64,
+    /** Sorted by `from`, starting from 0, with no two neighbours the same */
+    pieces: Vec<Piece>
+}
+
+/**
+ * (a + b) mod size, for a and b already on the dial, without overflowing
+ */
+fn add_mod(a:u64, b:u64, size:u64) -> u64 {
+    if a >= size - b { a - (size - b) } else { a + b }
+}
+
+impl Segment {
+    /**
+     * The segment of no movements, which leaves the dial where it is
+     */
+    pub fn identity(size:u64) -> Segment {
+        Segment { size, offset: 0, pieces: vec![Piece { from: 0, zero_counter: 0, zero_click_counter: 0 }] }
+    }
+
+    /**
+     * Builds the pieces by working out the counts at each starting position where they might change
+     */
+    fn tabulate(size:u64, offset:u64, mut breaks:Vec<u64>, counts:impl Fn(u64) -> (u64, u64)) -> Segment {
+        breaks.push(0);
+        breaks.sort_unstable();
+        breaks.dedup();
+        let mut pieces:Vec<Piece> = vec![];
+        for from in breaks.into_iter().filter(|b| *b < size) {
+            let (zero_counter, zero_click_counter) = counts(from);
+            if pieces.last().is_none_or(|p| (p.zero_counter, p.zero_click_counter)!=(zero_counter, zero_click_counter)) {
+                pieces.push(Piece { from, zero_counter, zero_click_counter });
+            }
+        }
+        Segment { size, offset, pieces }
+    }
+
+    /**
+     * The segment for a single movement.  It lands on 0 from just one starting position, and clicks onto it the same
+     * number of times from every position, apart from one range which gets there once more
+     */
+    pub fn movement(movement:Movement, size:u64, mode:DialMode) -> Segment {
+        let (turns, steps) = mode.split(movement.distance(), size);
+        let offset = match movement {
+            Movement::Right(_)=>steps,
+            Movement::Left(_)=>(size - steps) % size
+        };
+        let lands_from = (size - offset) % size;
+        let mut breaks = vec![lands_from, lands_from.saturating_add(1)];
+        if steps > 0 {
+            match movement {
+                Movement::Right(_)=>breaks.push(size - steps),
+                Movement::Left(_)=>breaks.extend([1, steps.saturating_add(1)])
+            }
+        }
+        Segment::tabulate(size, offset, breaks, |start| {
+            let clicks = turns.saturating_add(clicks_on(start, size, movement.with_distance(steps), 0));
+            ((start==lands_from) as u64, clicks)
+        })
+    }
+
+    /**
+     * Summarises the movements by splitting them in half, summarising each half in parallel and joining the results
+     */
+    pub fn of(movements:&[Movement], size:u64, mode:DialMode) -> Segment {
+        match movements {
+            []=>Segment::identity(size),
+            [movement]=>Segment::movement(*movement, size, mode),
+            _=>{
+                let (first, second) = movements.split_at(movements.len() / 2);
+                let (first, second) = rayon::join(|| Segment::of(first, size, mode), || Segment::of(second, size, mode));
+                first.then(&second)
+            }
+        }
+    }
+
+    fn counts_at(&self, start:u64) -> (u64, u64) {
+        let piece = &self.pieces[self.pieces.partition_point(|p| p.from <= start) - 1];
+        (piece.zero_counter, piece.zero_click_counter)
+    }
+
+    /**
+     * This segment followed by `next`.  The second segment starts wherever this one finished, so its pieces are
+     * shifted back by this one's offset to line them up with where the whole thing started
+     */
+    pub fn then(&self, next:&Segment) -> Segment {
+        assert_eq!(self.size, next.size, "segments of different sized dials can't be joined");
+        let size = self.size;
+        let back = (size - self.offset) % size;
+        let mut breaks:Vec<u64> = self.pieces.iter().map(|p| p.from).collect();
+        breaks.extend(next.pieces.iter().map(|p| add_mod(p.from, back, size)));
+        Segment::tabulate(size, add_mod(self.offset, next.offset, size), breaks, |start| {
+            let (zeros, clicks) = self.counts_at(start);
+            let (next_zeros, next_clicks) = next.counts_at(add_mod(start, self.offset, size));
+            (zeros.saturating_add(next_zeros), clicks.saturating_add(next_clicks))
+        })
+    }
+
+    /**
+     * Where a dial starting at `start` ends up, and how much its zero_counter and zero_click_counter go up by
+     */
+    pub fn apply(&self, start:u64) -> (u64, u64, u64) {
+        let (zeros, clicks) = self.counts_at(start);
+        (add_mod(start, self.offset, self.size), zeros, clicks)
+    }
+}
+
+/**
+ * Works out the state the dial would be in after turning it through the movements, exactly as `turn` would, but
+ * splitting the work across threads.  Clamped dials can't be summarised by an offset, and notches aren't tracked,
+ * so both are refused.  The error can be sent between threads, so it comes back from `evaluate_logs` untouched
+ */
+pub fn evaluate(dial:&SafeDial, movements:&[Movement]) -> Result<DialState, Box<dyn Error + Send + Sync>> {
+    if dial.mode()==DialMode::Clamped {
+        return Err("clamped dials can't be evaluated in parallel, as they don't just move by an offset".into())
+    }
+    if !dial.notch_counts().is_empty() {
+        return Err("notches aren't counted when evaluating in parallel, turn the dial through the movements instead".into())
+    }
+    let (position, zeros, clicks) = Segment::of(movements, dial.size(), dial.mode()).apply(dial.position);
+    let mut state = dial.state();
+    state.position = position;
+    state.zero_counter = state.zero_counter.saturating_add(zeros);
+    state.zero_click_counter = state.zero_click_counter.saturating_add(clicks);
+    Ok(state)
+}
+
+/**
+ * Evaluates each of the logs from the same starting dial, in parallel
+ */
+pub fn evaluate_logs(dial:&SafeDial, logs:&[Vec<Movement>]) -> Result<Vec<DialState>, Box<dyn Error + Send + Sync>> {
+    logs.par_iter().map(|log| evaluate(dial, log)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, EXAMPLE};
+
+    fn sequential(dial:&SafeDial, movements:&[Movement]) -> DialState {
+        let mut copy = SafeDial::from_state(dial.state()).unwrap();
+        for m in movements {
+            copy.turn(*m);
+        }
+        copy.state()
+    }
+
+    #[test]
+    fn test_example() {
+        let dial = SafeDial::new(50, 100).unwrap();
+        let movements = parse_input(EXAMPLE).unwrap();
+        let state = evaluate(&dial, &movements).unwrap();
+        assert_eq!((state.position, state.zero_counter, state.zero_click_counter), (32, 3, 6));
+        assert_eq!(state, sequential(&dial, &movements));
+
+        //The same summary works from anywhere
+        let segment = Segment::of(&movements, 100, DialMode::Wrapping);
+        for start in 0..100 {
+            let dial = SafeDial::new(start, 100).unwrap();
+            let after = sequential(&dial, &movements);
+            assert_eq!(segment.apply(start), (after.position, after.zero_counter, after.zero_click_counter), "from {}", start);
+        }
+    }
+
+    #[test]
+    fn test_associative() {
+        let movements = parse_input(EXAMPLE).unwrap();
+        let segments:Vec<Segment> = movements.iter().map(|m| Segment::movement(*m, 100, DialMode::Wrapping)).collect();
+        let identity = Segment::identity(100);
+        for i in 0..segments.len() - 2 {
+            let (a, b, c) = (&segments[i], &segments[i+1], &segments[i+2]);
+            assert_eq!(a.then(b).then(c), a.then(&b.then(c)));
+            assert_eq!(identity.then(a), *a);
+            assert_eq!(a.then(&identity), *a);
+        }
+        let folded = segments.iter().fold(identity, |acc, s| acc.then(s));
+        assert_eq!(folded, Segment::of(&movements, 100, DialMode::Wrapping));
+    }
+
+    #[test]
+    fn test_modes() {
+        let movements = parse_input("R3\nL7\nR5\nL40\nR19\nL0\n").unwrap();
+        let detented = SafeDial::with_mode(5, 20, DialMode::Detented(5)).unwrap();
+        assert_eq!(evaluate(&detented, &movements).unwrap(), sequential(&detented, &movements));
+
+        let mut huge = SafeDial::new(u64::MAX - 1, u64::MAX).unwrap();
+        huge.turn(Movement::Right(1));
+        let movements = vec![Movement::Right(u64::MAX), Movement::Left(u64::MAX - 1), Movement::Left(2), Movement::Right(u64::MAX / 2)];
+        assert_eq!(evaluate(&huge, &movements).unwrap(), sequential(&huge, &movements));
+
+        assert!(evaluate(&SafeDial::with_mode(0, 10, DialMode::Clamped).unwrap(), &movements).is_err());
+        let mut notched = SafeDial::new(0, 10).unwrap();
+        notched.add_notch(5).unwrap();
+        assert!(evaluate(&notched, &movements).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_logs() {
+        let dial = SafeDial::new(50, 100).unwrap();
+        let logs = vec![parse_input(EXAMPLE).unwrap(), vec![], parse_input("R50\nL200").unwrap()];
+        let states = evaluate_logs(&dial, &logs).unwrap();
+        let expected:Vec<DialState> = logs.iter().map(|log| sequential(&dial, log)).collect();
+        assert_eq!(states, expected);
+        assert_eq!(states[1], dial.state());
+
+        let clamped = SafeDial::with_mode(0, 10, DialMode::Clamped).unwrap();
+        assert_eq!(evaluate_logs(&clamped, &logs).unwrap_err().to_string(), evaluate(&clamped, &[]).unwrap_err().to_string());
+    }
+}
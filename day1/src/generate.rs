@@ -0,0 +1,39 @@
+use rand::Rng;
+use crate::Movement;
+
+/**
+ * A random list of dial movements.  Roughly a quarter of them are whole turns of the dial, as those are where the
+ * counting is easiest to get wrong
+ */
+pub fn movements(rng:&mut impl Rng, count:usize, max_steps:u64) -> Vec<Movement> {
+    (0..count).map(|_| {
+        let steps = if rng.random_bool(0.25) {
+            100 * rng.random_range(0..=max_steps / 100)
+        } else {
+            rng.random_range(0..=max_steps)
+        };
+        if rng.random_bool(0.5) {
+            Movement::Left(steps)
+        } else {
+            Movement::Right(steps)
+        }
+    }).collect()
+}
+
+/**
+ * Renders movements the way the puzzle input writes them, one per line
+ */
+pub fn to_input(movements:&[Movement]) -> String {
+    movements.iter().map(|m| match m {
+        Movement::Left(n)=>format!("L{}\n", n),
+        Movement::Right(n)=>format!("R{}\n", n)
+    }).collect()
+}
+
+/**
+ * A random puzzle input of up to `max_count` movements
+ */
+pub fn input(rng:&mut impl Rng, max_count:usize) -> String {
+    let count = rng.random_range(1..=max_count);
+    to_input(&movements(rng, count, 400))
+}
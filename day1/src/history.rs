@@ -0,0 +1,283 @@
+use std::{collections::BTreeMap, error::Error};
+use common::{json, Value};
+use crate::{DialMode, Movement, NotchCount, SafeDial};
+
+/**
+ * Everything needed to pick a dial up where it left off: its size and mode, where it points, its counters and its notches.
+ * It converts to and from JSON, so that a long movement log can be checkpointed and resumed
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialState {
+    pub size: u64,
+    pub mode: DialMode,
+    pub position: u64,
+    pub zero_counter: u64,
+    pub zero_click_counter: u64,
+    pub notches: BTreeMap<u64, NotchCount>
+}
+
+impl DialState {
+    pub fn to_json(&self) -> Value {
+        let notches:Vec<Value> = self.notches.iter()
+            .map(|(position, count)| json!({ "position": position, "landings": count.landings, "passes": count.passes }))
+            .collect();
+        let mode = match self.mode {
+            DialMode::Wrapping=>json!({ "mode": "wrapping" }),
+            DialMode::Clamped=>json!({ "mode": "clamped" }),
+            DialMode::Detented(k)=>json!({ "mode": "detented", "detent": k })
+        };
+        json!({
+            "size": self.size,
+            "mode": mode,
+            "position": self.position,
+            "zero_counter": self.zero_counter,
+            "zero_click_counter": self.zero_click_counter,
+            "notches": notches
+        })
+    }
+
+    /**
+     * Reads a state written by `to_json`.  States saved before dials had modes are read as wrapping dials
+     */
+    pub fn from_json(value:&Value) -> Result<DialState, Box<dyn Error>> {
+        let number = |v:&Value, field:&str| v.get(field)
+            .and_then(|n| n.as_u64())
+            .ok_or_else(|| format!("the dial state needs a whole number for {}", field));
+
+        let mode = match value.get("mode") {
+            None=>DialMode::Wrapping,
+            Some(mode)=>match mode.get("mode").and_then(|m| m.as_str()) {
+                Some("wrapping")=>DialMode::Wrapping,
+                Some("clamped")=>DialMode::Clamped,
+                Some("detented")=>DialMode::Detented(number(mode, "detent")?),
+                _=>return Err(format!("{} is not a dial mode", mode).into())
+            }
+        };
+
+        let mut notches:BTreeMap<u64, NotchCount> = BTreeMap::new();
+        if let Some(list) = value.get("notches") {
+            let list = list.as_array().ok_or("the dial state's notches should be a list")?;
+            for notch in list {
+                notches.insert(number(notch, "position")?, NotchCount { landings: number(notch, "landings")?, passes: number(notch, "passes")? });
+            }
+        }
+
+        let state = DialState {
+            size: number(value, "size")?,
+            mode,
+            position: number(value, "position")?,
+            zero_counter: number(value, "zero_counter")?,
+            zero_click_counter: number(value, "zero_click_counter")?,
+            notches
+        };
+        state.check()?;
+        Ok(state)
+    }
+
+    /**
+     * Checks that a dial could be in this state: its position and notches are all on it, and it suits its mode
+     */
+    pub fn check(&self) -> Result<(), Box<dyn Error>> {
+        self.mode.check(self.position, self.size)?;
+        if let Some(off) = self.notches.keys().find(|p| **p >= self.size) {
+            return Err(format!("a notch at {} is off a dial of size {}", off, self.size).into())
+        }
+        Ok( () )
+    }
+}
+
+impl SafeDial {
+    /**
+     * The dial's current state, for saving and resuming later
+     */
+    pub fn state(&self) -> DialState {
+        DialState {
+            size: self.size,
+            mode: self.mode,
+            position: self.position,
+            zero_counter: self.zero_counter,
+            zero_click_counter: self.zero_click_counter,
+            notches: self.notches.clone()
+        }
+    }
+
+    /**
+     * Picks a dial up from a saved state, once it has checked the state makes sense.  It doesn't keep a history
+     * unless asked to with `with_history`
+     */
+    pub fn from_state(state:DialState) -> Result<SafeDial, Box<dyn Error>> {
+        state.check()?;
+        Ok(SafeDial {
+            position: state.position,
+            size: state.size,
+            mode: state.mode,
+            zero_counter: state.zero_counter,
+            zero_click_counter: state.zero_click_counter,
+            notches: state.notches.clone(),
+            turned: false,
+            initial: state,
+            history: None
+        })
+    }
+
+    /**
+     * Starts keeping a history of the dial's movements from where it is now, so that they can be undone and replayed.
+     * Each movement keeps a copy of the dial's state, notches and all, so only dials that need it should keep one
+     */
+    pub fn with_history(mut self) -> SafeDial {
+        if self.history.is_none() {
+            self.initial = self.state();
+            self.history = Some(vec![]);
+        }
+        self
+    }
+
+    /**
+     * The movements made since the dial started keeping its history, oldest first.  There are none if it isn't keeping one
+     */
+    pub fn movements(&self) -> impl Iterator<Item = Movement> + '_ {
+        self.history.iter().flatten().map(|(movement, _)| *movement)
+    }
+
+    /**
+     * Takes back the last movement, putting the position and all the counters back exactly as they were.
+     * Returns the movement that was undone, or None if there was nothing to undo or the dial isn't keeping a history
+     */
+    pub fn undo(&mut self) -> Option<Movement> {
+        let (movement, before) = self.history.as_mut()?.pop()?;
+        self.position = before.position;
+        self.zero_counter = before.zero_counter;
+        self.zero_click_counter = before.zero_click_counter;
+        self.notches = before.notches;
+        Some(movement)
+    }
+
+    /**
+     * Builds a new dial from where this one started keeping its history, by replaying the first `n` movements of it.
+     * The new dial keeps a history too.  Returns None if there haven't been that many movements
+     */
+    pub fn replay_to(&self, n:usize) -> Option<SafeDial> {
+        let movements = self.history.as_ref()?.get(..n)?;
+        //The initial state was checked when the dial was made, so it can't be refused now
+        let mut dial = SafeDial::from_state(self.initial.clone()).ok()?.with_history();
+        for (movement, _) in movements {
+            dial.turn(*movement);
+        }
+        Some(dial)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_input, EXAMPLE};
+
+    fn example_dial() -> SafeDial {
+        let mut dial = SafeDial::new(50, 100).unwrap().with_history();
+        dial.add_notch(82).unwrap();
+        for m in parse_input(EXAMPLE).unwrap() {
+            dial.turn(m);
+        }
+        dial
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut dial = example_dial();
+        let states:Vec<DialState> = (0..=10).map(|n| dial.replay_to(n).unwrap().state()).collect();
+        assert_eq!(dial.state(), states[10]);
+        assert_eq!(dial.movements().count(), 10);
+
+        for n in (0..10).rev() {
+            assert_eq!(dial.undo(), parse_input(EXAMPLE).unwrap().get(n).copied());
+            assert_eq!(dial.state(), states[n]);
+        }
+        assert_eq!(dial.undo(), None);
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (50, 0, 0));
+
+        //Undo a whole turn from zero, which is where the counters are easiest to get wrong
+        let mut dial = SafeDial::new(0, 100).unwrap().with_history();
+        dial.turn(Movement::Right(200));
+        dial.undo();
+        assert_eq!(dial.state(), SafeDial::new(0, 100).unwrap().state());
+    }
+
+    #[test]
+    fn test_replay_to() {
+        let dial = example_dial();
+        let replayed = dial.replay_to(3).unwrap();
+        assert_eq!((replayed.position, replayed.zero_counter, replayed.zero_click_counter), (0, 1, 2));
+        assert_eq!(replayed.movements().collect::<Vec<Movement>>(), dial.movements().take(3).collect::<Vec<Movement>>());
+        assert_eq!(dial.replay_to(10).unwrap().state(), dial.state());
+        assert!(dial.replay_to(11).is_none());
+
+        let mut turned = SafeDial::new(50, 100).unwrap();
+        turned.turn(Movement::Left(1));
+        assert!(turned.add_notch(3).is_err());
+    }
+
+    #[test]
+    fn test_without_history() {
+        let mut dial = SafeDial::new(50, 100).unwrap();
+        for m in parse_input(EXAMPLE).unwrap() {
+            dial.turn(m);
+        }
+        assert_eq!(dial.movements().count(), 0);
+        assert_eq!(dial.undo(), None);
+        assert!(dial.replay_to(0).is_none());
+        assert_eq!((dial.position, dial.zero_counter), (32, 3));
+
+        //History starts from wherever the dial is when it's asked for
+        let mut dial = dial.with_history();
+        dial.turn(Movement::Right(18));
+        assert_eq!(dial.replay_to(0).unwrap().position, 32);
+        assert_eq!(dial.undo(), Some(Movement::Right(18)));
+        assert_eq!(dial.undo(), None);
+        assert_eq!((dial.position, dial.zero_counter), (32, 3));
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let movements = parse_input(EXAMPLE).unwrap();
+        let mut first_half = SafeDial::new(50, 100).unwrap();
+        first_half.add_notch(82).unwrap();
+        for m in movements[..5].iter() {
+            first_half.turn(*m);
+        }
+        let saved = first_half.state().to_json().to_string();
+
+        let mut resumed = SafeDial::from_state(DialState::from_json(&saved.parse::<Value>().unwrap()).unwrap()).unwrap().with_history();
+        for m in movements[5..].iter() {
+            resumed.turn(*m);
+        }
+        assert_eq!(resumed.state(), example_dial().state());
+        //History starts again from the checkpoint
+        assert_eq!(resumed.movements().count(), 5);
+        assert_eq!(resumed.replay_to(0).unwrap().state(), first_half.state());
+    }
+
+    #[test]
+    fn test_state_json() {
+        let state = example_dial().state();
+        assert_eq!(state.to_json(), json!({
+            "size": 100, "mode": { "mode": "wrapping" }, "position": 32, "zero_counter": 3, "zero_click_counter": 6,
+            "notches": [{ "position": 82, "landings": 1, "passes": 4 }]
+        }));
+        assert_eq!(DialState::from_json(&state.to_json()).unwrap(), state);
+
+        let detented = SafeDial::with_mode(5, 20, DialMode::Detented(5)).unwrap().state();
+        assert_eq!(detented.to_json()["mode"], json!({ "mode": "detented", "detent": 5 }));
+        assert_eq!(DialState::from_json(&detented.to_json()).unwrap(), detented);
+        let clamped = SafeDial::with_mode(5, 20, DialMode::Clamped).unwrap().state();
+        assert_eq!(DialState::from_json(&clamped.to_json()).unwrap(), clamped);
+        assert!(DialState::from_json(&json!({ "size": 20, "mode": { "mode": "detented", "detent": 3 }, "position": 0, "zero_counter": 0, "zero_click_counter": 0 })).is_err());
+        assert!(DialState::from_json(&json!({ "size": 20, "mode": "sticky", "position": 0, "zero_counter": 0, "zero_click_counter": 0 })).is_err());
+        let huge = SafeDial::new(u64::MAX - 1, u64::MAX).unwrap().state();
+        assert_eq!(DialState::from_json(&huge.to_json()).unwrap(), huge);
+
+        assert!(DialState::from_json(&json!({ "size": 10, "position": 10, "zero_counter": 0, "zero_click_counter": 0 })).is_err());
+        assert!(DialState::from_json(&json!({ "size": 0, "position": 0, "zero_counter": 0, "zero_click_counter": 0 })).is_err());
+        assert!(DialState::from_json(&json!({ "size": 10, "position": 1, "zero_counter": -1, "zero_click_counter": 0 })).is_err());
+        assert!(DialState::from_json(&json!({ "size": 10, "position": 1 })).is_err());
+    }
+}
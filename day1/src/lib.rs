@@ -0,0 +1,807 @@
+use std::{collections::BTreeMap, error::Error, fmt::Display};
+use common::{json, numbered_lines, trace, Example, ParseError, Puzzle, Value};
+
+pub mod generate;
+/**
+ * Locks made of several dials, which can be geared together like an odometer
+ */
+pub mod lock;
+/**
+ * Working out the quickest way to dial a combination
+ */
+pub mod solver;
+/**
+ * Undoing and replaying movements, and saving a dial's state so that it can be picked up again later
+ */
+pub mod history;
+/**
+ * Statistics about a movement log, such as where the dial lands and how long it goes without touching 0
+ */
+pub mod stats;
+/**
+ * Evaluating long movement logs in parallel, by summarising parts of them and joining the summaries in order
+ */
+pub mod fold;
+
+use history::DialState;
+
+/**
+ * How far the dial has to go in the movement's direction to get from `from` to `to`, or a whole turn if they are
+ * the same position.  Both must be on the dial
+ */
+pub(crate) fn distance_to(from:u64, to:u64, size:u64, movement:Movement) -> u64 {
+    let (ahead, behind) = match movement {
+        Movement::Right(_)=>(to, from),
+        Movement::Left(_)=>(from, to)
+    };
+    if ahead > behind {
+        ahead - behind
+    } else {
+        size - (behind - ahead)
+    }
+}
+
+/**
+ * How many of the clicks in the movement land on `target`, worked out without stepping through them.  The first
+ * lands on it after `distance_to` clicks, and then every whole turn after that
+ */
+fn clicks_on(position:u64, size:u64, movement:Movement, target:u64) -> u64 {
+    let first = distance_to(position, target, size, movement);
+    let distance = movement.distance();
+    if distance < first {
+        0
+    } else {
+        (distance - first) / size + 1
+    }
+}
+
+/**
+ * How a dial behaves at its ends and where it can come to rest.  Whatever the mode, `zero_counter` counts movements
+ * that finish on 0 and `zero_click_counter` counts every click onto 0, and notches are counted the same way
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DialMode {
+    /** The dial wraps around from its last position to 0 and back again */
+    #[default]
+    Wrapping,
+    /**
+     * The dial has hard stops at 0 and its last position.  A movement that would go past a stop leaves the dial
+     * against it, and the clicks it couldn't make are lost: pushing against 0 doesn't click onto it again, though
+     * finishing there still counts as landing on it
+     */
+    Clamped,
+    /**
+     * The dial wraps, but only comes to rest on every k-th position, starting at 0, and k must divide the size.
+     * A movement that would finish between detents carries on in the same direction to the next one, and the
+     * clicks it makes on the way count like any others
+     */
+    Detented(u64)
+}
+
+impl DialMode {
+    /**
+     * Checks that a dial of this mode can have `size` positions and rest at `position`
+     */
+    pub fn check(&self, position:u64, size:u64) -> Result<(), Box<dyn Error>> {
+        if size==0 {
+            return Err("a dial must have at least one position".into())
+        }
+        if position >= size {
+            return Err(format!("position {} is off a dial of size {}", position, size).into())
+        }
+        if let DialMode::Detented(k) = self {
+            if *k==0 || !size.is_multiple_of(*k) {
+                return Err(format!("detents every {} position(s) don't fit evenly round a dial of size {}", k, size).into())
+            }
+            if !position.is_multiple_of(*k) {
+                return Err(format!("position {} is between the detents, which are every {} position(s)", position, k).into())
+            }
+        }
+        Ok( () )
+    }
+
+    /**
+     * Splits the distance a wrapping or detented dial actually travels into whole turns and the steps left over,
+     * without ever adding them back together, as that could overflow.  With detents the steps are rounded up to
+     * the next detent, which may make a whole turn
+     */
+    pub(crate) fn split(&self, distance:u64, size:u64) -> (u64, u64) {
+        let (turns, steps) = (distance / size, distance % size);
+        match self {
+            DialMode::Detented(k)=>{
+                //size is a multiple of k, so this is at most size
+                let settled = steps.div_ceil(*k) * k;
+                if settled==size { (turns + 1, 0) } else { (turns, settled) }
+            },
+            _=>(turns, steps)
+        }
+    }
+}
+
+impl Display for DialMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DialMode::Wrapping=>write!(f, "wrapping"),
+            DialMode::Clamped=>write!(f, "clamped"),
+            DialMode::Detented(k)=>write!(f, "detented every {}", k)
+        }
+    }
+}
+
+/**
+ * A dial numbered from 0 to size-1, which by default wraps around in both directions.  Sizes and movements can be as
+ * large as a u64 will go; the counters saturate rather than overflow, though it would take over 2^64 clicks to get there
+ */
+pub struct SafeDial {
+    pub position: u64,
+    size: u64,
+    mode: DialMode,
+    pub zero_counter: u64,
+    pub zero_click_counter: u64,
+    notches: BTreeMap<u64, NotchCount>,
+    /** Whether the dial has been turned at all, as notches can only be added before then */
+    turned: bool,
+    /** Where the dial was before any of the movements in the history were made */
+    initial: DialState,
+    /**
+     * Each movement made, along with the state of the dial just before it.  This is None unless the dial was asked
+     * to keep its history with `with_history`, as it grows with every turn
+     */
+    history: Option<Vec<(Movement, DialState)>>
+}
+
+/**
+ * How often the dial has touched a notch, counted the same way as the zero counters: `landings` is movements that
+ * finished on it, and `passes` is every click onto it, including the last click of a movement that lands there
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NotchCount {
+    pub landings: u64,
+    pub passes: u64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Movement {
+    Left(u64),
+    Right(u64)
+}
+
+impl Movement {
+    /**
+     * How many clicks the movement turns the dial, whichever way it goes
+     */
+    pub fn distance(&self) -> u64 {
+        match self {
+            Movement::Left(n)|Movement::Right(n)=>*n
+        }
+    }
+
+    /**
+     * A movement in the same direction, but of a different distance
+     */
+    pub fn with_distance(&self, distance:u64) -> Movement {
+        match self {
+            Movement::Left(_)=>Movement::Left(distance),
+            Movement::Right(_)=>Movement::Right(distance)
+        }
+    }
+}
+
+/**
+ * What happened to the dial during one movement
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurnEvent {
+    pub movement: Movement,
+    pub start: u64,
+    pub end: u64,
+    /**
+     * How many clicks the dial actually travelled, which is less than the movement asked for if it hit a stop, or more
+     * if it carried on to a detent.  It saturates for movements of more than 2^64 clicks
+     */
+    pub clicks: u64,
+    /** How many clicks on 0 the movement made, which is what zero_click_counter goes up by */
+    pub zero_passes: u64,
+    /** Whether the movement finished on 0, which is what zero_counter counts */
+    pub landed_on_zero: bool,
+    /** Whether the dial went across the boundary between its last position and 0 at some point, which a clamped dial never does */
+    pub wrapped: bool
+}
+
+impl SafeDial {
+    /**
+     * Creates a wrapping dial of `size` positions, pointing at `initial_position`.  The size must be at least 1 and
+     * the position must be on the dial
+     */
+    pub fn new(initial_position:u64, size:u64) -> Result<SafeDial, Box<dyn Error>> {
+        SafeDial::with_mode(initial_position, size, DialMode::Wrapping)
+    }
+
+    /**
+     * Creates a dial that behaves according to `mode`, checking that the size and position make sense for it
+     */
+    pub fn with_mode(initial_position:u64, size:u64, mode:DialMode) -> Result<SafeDial, Box<dyn Error>> {
+        mode.check(initial_position, size)?;
+        Ok(SafeDial {
+            position: initial_position,
+            size,
+            mode,
+            zero_counter: 0,
+            zero_click_counter: 0,
+            notches: BTreeMap::new(),
+            turned: false,
+            initial: DialState { size, mode, position: initial_position, zero_counter: 0, zero_click_counter: 0, notches: BTreeMap::new() },
+            history: None
+        })
+    }
+
+    /**
+     * Starts counting how often the dial lands on and passes `position`.  Adding a notch twice keeps its counts.
+     * Notches have to be added before the dial is turned, so that replaying its history counts them the same way
+     */
+    pub fn add_notch(&mut self, position:u64) -> Result<(), Box<dyn Error>> {
+        if position >= self.size {
+            return Err(format!("a notch at {} is off a dial of size {}", position, self.size).into())
+        }
+        if self.turned {
+            return Err("notches must be added before the dial is turned".into())
+        }
+        self.notches.entry(position).or_default();
+        self.initial.notches.entry(position).or_default();
+        Ok( () )
+    }
+
+    pub fn notch(&self, position:u64) -> Option<NotchCount> {
+        self.notches.get(&position).copied()
+    }
+
+    pub fn notch_counts(&self) -> &BTreeMap<u64, NotchCount> {
+        &self.notches
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn mode(&self) -> DialMode {
+        self.mode
+    }
+
+    /**
+     * Turns the dial, updating the counters, and returns what happened
+     */
+    pub fn turn(&mut self, movement: Movement) -> TurnEvent {
+        if self.history.is_some() {
+            let before = self.state();
+            self.history.get_or_insert_default().push((movement, before));
+        }
+        self.turned = true;
+        if self.mode==DialMode::Clamped {
+            return self.turn_clamped(movement)
+        }
+        let (start, clicks_before) = (self.position, self.zero_click_counter);
+        //If we go once around then we go to the same place, so we only want the remainder; each whole turn clicks past 0 once
+        let (turns, steps) = self.mode.split(movement.distance(), self.size);
+        for (notch, count) in self.notches.iter_mut() {
+            let clicks = turns.saturating_add(clicks_on(start, self.size, movement.with_distance(steps), *notch));
+            count.passes = count.passes.saturating_add(clicks);
+        }
+        self.zero_click_counter = self.zero_click_counter.saturating_add(turns);
+        //Everything below stays within 0..size, so that none of it can overflow however big the dial is
+        let wrapped = match movement {
+            Movement::Left(_)=>{
+                let wrapped = steps > self.position;
+                if wrapped {    //we are wrapping around at 0
+                    self.position = self.size - (steps - self.position);
+                    //Leaving 0 isn't a click onto it, but every other wrap to the left goes through it.
+                    //(We can only wrap from 0 with some steps to go, so we don't land on it either)
+                    if start!=0 {
+                        self.zero_click_counter = self.zero_click_counter.saturating_add(1);
+                    }
+                } else {
+                    self.position -= steps;
+                }
+                wrapped
+            },
+            Movement::Right(_)=>{
+                //the distance to the end is at least 1, so no steps never wraps
+                let wrapped = steps >= self.size - self.position;
+                if wrapped {
+                    //We wrapped around
+                    self.zero_click_counter = self.zero_click_counter.saturating_add(1);
+                    self.position = steps - (self.size - self.position);
+                } else {
+                    self.position += steps;
+                }
+                wrapped
+            }
+        };
+        //If the movement was only whole rotations then we haven't moved past them, and the click that landed us here
+        //was already counted as one of them
+        let partial_turn = steps > 0;
+        if self.position==0 {
+            self.zero_counter = self.zero_counter.saturating_add(1);
+            if !wrapped && partial_turn {   //don't double-count wraparounds.  If we already detected a zero wrap don't count it again here.
+                self.zero_click_counter = self.zero_click_counter.saturating_add(1);
+            }
+        }
+        if let Some(count) = self.notches.get_mut(&self.position) {
+            count.landings = count.landings.saturating_add(1);
+        }
+        trace!(?movement, position = self.position, zeros = self.zero_counter, zero_clicks = self.zero_click_counter, "turned");
+
+        TurnEvent {
+            movement,
+            start,
+            end: self.position,
+            clicks: turns.saturating_mul(self.size).saturating_add(steps),
+            zero_passes: self.zero_click_counter - clicks_before,
+            landed_on_zero: self.position==0,
+            wrapped: wrapped || turns > 0
+        }
+    }
+
+    /**
+     * Turns a dial with hard stops, which can't wrap.  It only clicks onto a position by arriving at it, so each
+     * movement passes a position at most once, and a dial that starts on 0 can't click onto it at all
+     */
+    fn turn_clamped(&mut self, movement:Movement) -> TurnEvent {
+        let start = self.position;
+        self.position = match movement {
+            Movement::Left(n)=>start.saturating_sub(n),
+            Movement::Right(n)=>start + n.min(self.size - 1 - start)
+        };
+        let (low, high) = (start.min(self.position), start.max(self.position));
+        //Every position between where we started and where we stopped was clicked onto once, apart from the start
+        let passed = |p:u64| p >= low && p <= high && p!=start;
+
+        for (notch, count) in self.notches.iter_mut() {
+            count.passes = count.passes.saturating_add(passed(*notch) as u64);
+        }
+        if let Some(count) = self.notches.get_mut(&self.position) {
+            count.landings = count.landings.saturating_add(1);
+        }
+        let zero_passes = passed(0) as u64;
+        self.zero_click_counter = self.zero_click_counter.saturating_add(zero_passes);
+        if self.position==0 {
+            self.zero_counter = self.zero_counter.saturating_add(1);
+        }
+        trace!(?movement, position = self.position, zeros = self.zero_counter, zero_clicks = self.zero_click_counter, "turned against the stops");
+
+        TurnEvent {
+            movement,
+            start,
+            end: self.position,
+            clicks: high - low,
+            zero_passes,
+            landed_on_zero: self.position==0,
+            wrapped: false
+        }
+    }
+
+    /**
+     * Lazily applies each movement in turn, yielding what happened for each of them.  The dial is left wherever the
+     * last movement taken from the iterator put it
+     */
+    pub fn events<'a>(&'a mut self, movements:impl IntoIterator<Item = Movement> + 'a) -> impl Iterator<Item = TurnEvent> + 'a {
+        movements.into_iter().map(move |m| self.turn(m))
+    }
+}
+
+/**
+ * Parses a single movement such as L68.  `text` must be a slice of `line`, so that errors can point at where it is.
+ * As well as L and R, our logs write CCW and CW, or - and +, and any of them may be in lower case
+ */
+pub(crate) fn parse_movement(line:&str, text:&str) -> Result<Movement, ParseError> {
+    //the direction is everything up to the first digit, and the rest is the step count
+    let (dirn, steps) = text.split_at(text.find(|ch:char| ch.is_ascii_digit()).unwrap_or(text.len()));
+    let left = match dirn.to_ascii_uppercase().as_str() {
+        "L"|"CCW"|"-"=>true,
+        "R"|"CW"|"+"=>false,
+        ""=>return Err(ParseError::at(line, text, format!("'{}' has no direction, expected L, R, CW, CCW, + or - before it", text))),
+        _=>return Err(ParseError::at(line, dirn, format!("invalid direction '{}', expected L, R, CW, CCW, + or -", dirn)))
+    };
+    if steps.is_empty() {
+        return Err(ParseError::at(line, text, format!("'{}' is missing the number of steps", text)))
+    }
+    let step_count = steps.parse::<u64>().map_err(|e| {
+        ParseError::at(line, steps, format!("'{}' is not a valid number of steps: {}", steps, e))
+    })?;
+    Ok(if left { Movement::Left(step_count) } else { Movement::Right(step_count) })
+}
+
+/**
+ * Every line of the input that couldn't be parsed, rather than just the first
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidLines(pub Vec<ParseError>);
+
+impl Display for InvalidLines {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let errors:Vec<String> = self.0.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", errors.join("\n"))
+    }
+}
+
+impl Error for InvalidLines { }
+
+/**
+ * Parses every movement in the input.  There can be several on a line, separated by commas or whitespace.
+ * Every line is checked, and if any are invalid then all of them are reported, each with its line number
+ */
+pub fn parse_strict(file_content: &str) -> Result<Vec<Movement>, InvalidLines> {
+    let mut results:Vec<Movement> = vec![];
+    let mut errors:Vec<ParseError> = vec![];
+
+    for (line_number, line) in numbered_lines(file_content) {
+        let parsed:Result<Vec<Movement>, ParseError> = line.split(|ch:char| ch==',' || ch.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| parse_movement(line, token))
+            .collect();
+        match parsed {
+            Ok(movements)=>results.extend(movements),
+            Err(e)=>errors.push(e.on_line(line_number))
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(results)
+    } else {
+        Err(InvalidLines(errors))
+    }
+}
+
+pub fn parse_input(file_content: &str) -> Result<Vec<Movement>, Box<dyn Error>> {
+    Ok(parse_strict(file_content)?)
+}
+
+/**
+ * The worked example of dial movements from the puzzle text
+ */
+pub const EXAMPLE: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
+
+/**
+ * The dial starts at 50 on a 100-position wheel.  Part 1 counts landings on zero, part 2 counts every pass
+ */
+#[derive(Default)]
+pub struct Day1;
+
+impl Day1 {
+    /**
+     * Turns the puzzle's dial through the movements, from where it starts at 50
+     */
+    pub fn turn_dial(input:&[Movement]) -> Result<SafeDial, Box<dyn Error>> {
+        let mut dial = SafeDial::new(50, 100)?;
+        for m in input {
+            dial.turn(*m);
+        }
+        Ok(dial)
+    }
+}
+
+impl Puzzle for Day1 {
+    type Input = Vec<Movement>;
+    type Output = u64;
+
+    fn parse(&self, input:&str) -> Result<Vec<Movement>, Box<dyn Error>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input:&Vec<Movement>) -> Result<u64, Box<dyn Error>> {
+        Ok(Self::turn_dial(input)?.zero_counter)
+    }
+
+    fn part2(&self, input:&Vec<Movement>) -> Result<u64, Box<dyn Error>> {
+        Ok(Self::turn_dial(input)?.zero_click_counter)
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "3", part2: "6" }
+    }
+
+    fn details(&self, input:&Vec<Movement>) -> Result<Value, Box<dyn Error>> {
+        let dial = Self::turn_dial(input)?;
+        Ok(json!({ "movements": input.len(), "final_position": dial.position }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        let mut dial = SafeDial::new(5, 10).unwrap();
+        dial.turn(Movement::Left(1));
+        assert_eq!(dial.position, 4);
+        dial.turn(Movement::Right(2));
+        assert_eq!(dial.position, 6);
+        dial.turn(Movement::Left(10));
+        assert_eq!(dial.position, 6);
+        dial.turn(Movement::Right(25));
+        assert_eq!(dial.position, 1);
+    }
+
+    #[test]
+    fn test_rightedge() {
+        let mut dial = SafeDial::new(5, 10).unwrap();
+        dial.turn(Movement::Right(5));
+        assert_eq!(dial.position, 0);
+
+        let mut dial = SafeDial::new(9, 10).unwrap();
+        dial.turn(Movement::Right(1));
+        assert_eq!(dial.position, 0);
+    }
+
+    #[test]
+    fn test_leftedge() {
+        let mut dial = SafeDial::new(10, 100).unwrap();
+        dial.turn(Movement::Left(45));
+        assert_eq!(dial.position,65);
+    }
+
+    #[test]
+    fn test_example() {
+        let mut dial = SafeDial::new(50, 100).unwrap();
+        dial.turn(Movement::Left(68));
+        assert_eq!(dial.position, 82);
+        assert_eq!(dial.zero_click_counter, 1); // Should be 1 after first move
+        
+        dial.turn(Movement::Left(30));
+        assert_eq!(dial.position, 52);
+        assert_eq!(dial.zero_click_counter, 1); // Still 1
+        
+        dial.turn(Movement::Right(48));
+        assert_eq!(dial.position, 0);
+        assert_eq!(dial.zero_click_counter, 2); // Now 2 (wrap to 0)
+        
+        dial.turn(Movement::Left(5));
+        assert_eq!(dial.position, 95);
+        assert_eq!(dial.zero_click_counter, 2);
+
+        dial.turn(Movement::Right(60));
+        assert_eq!(dial.position, 55);
+        assert_eq!(dial.zero_click_counter, 3);
+        
+        dial.turn(Movement::Left(55));
+        assert_eq!(dial.position, 0);
+        assert_eq!(dial.zero_click_counter, 4);
+        
+        dial.turn(Movement::Left(1));
+        assert_eq!(dial.position, 99);
+        assert_eq!(dial.zero_click_counter, 4);
+        
+        dial.turn(Movement::Left(99));
+        assert_eq!(dial.position, 0);
+        assert_eq!(dial.zero_click_counter, 5);
+        
+        dial.turn(Movement::Right(14));
+        assert_eq!(dial.position, 14);
+        assert_eq!(dial.zero_click_counter, 5);
+        
+        dial.turn(Movement::Left(82));
+        assert_eq!(dial.position, 32);
+
+        assert_eq!(dial.zero_click_counter, 6);
+        
+        // Part 1: should be 3 (steps 4, 7, 9 end on 0)
+        assert_eq!(dial.zero_counter, 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input("L68\n\nR5\n").unwrap(), vec![Movement::Left(68), Movement::Right(5)]);
+
+        let e = parse_input("L68\nX30\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 1: invalid direction 'X', expected L, R, CW, CCW, + or -
+  X30
+  ^");
+        let e = parse_input("L68\nL30\nR4x\n").unwrap_err();
+        let e = &e.downcast_ref::<InvalidLines>().unwrap().0[0];
+        assert_eq!((e.line, e.column, e.width), (3, 2, 2));
+    }
+
+    #[test]
+    fn test_strict_parsing() {
+        //Every bad line is reported, not just the first
+        let InvalidLines(errors) = parse_strict("L12x\nR5\nR-5\n15\nL\nR3 Q2\n").unwrap_err();
+        let found:Vec<(usize, usize, &str)> = errors.iter().map(|e| (e.line, e.column, e.message.as_str())).collect();
+        assert_eq!(found, vec![
+            (1, 2, "'12x' is not a valid number of steps: invalid digit found in string"),
+            (3, 1, "invalid direction 'R-', expected L, R, CW, CCW, + or -"),
+            (4, 1, "'15' has no direction, expected L, R, CW, CCW, + or - before it"),
+            (5, 1, "'L' is missing the number of steps"),
+            (6, 4, "invalid direction 'Q', expected L, R, CW, CCW, + or -")
+        ]);
+    }
+
+    #[test]
+    fn test_alternative_notations() {
+        let expected = vec![Movement::Right(15), Movement::Left(15), Movement::Left(3), Movement::Right(7)];
+        assert_eq!(parse_strict("+15\n-15\nl3\nr7\n").unwrap(), expected);
+        assert_eq!(parse_strict("CW15\nccw15\nCCW3\ncw7").unwrap(), expected);
+        assert_eq!(parse_strict("+15, -15,l3 \t R7\n").unwrap(), expected);
+        assert_eq!(parse_strict("R15,L15\n\n  L3  R7").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_events() {
+        let mut dial = SafeDial::new(50, 100).unwrap();
+        let events:Vec<TurnEvent> = dial.events(parse_input(EXAMPLE).unwrap()).collect();
+        assert_eq!(events.len(), 10);
+        assert_eq!(events[0], TurnEvent { movement: Movement::Left(68), start: 50, end: 82, clicks: 68, zero_passes: 1, landed_on_zero: false, wrapped: true });
+        assert_eq!(events[2], TurnEvent { movement: Movement::Right(48), start: 52, end: 0, clicks: 48, zero_passes: 1, landed_on_zero: true, wrapped: true });
+        assert_eq!(events[5], TurnEvent { movement: Movement::Left(55), start: 55, end: 0, clicks: 55, zero_passes: 1, landed_on_zero: true, wrapped: false });
+
+        //The events add up to the counters
+        assert_eq!(events.iter().map(|e| e.zero_passes).sum::<u64>(), dial.zero_click_counter);
+        assert_eq!(events.iter().filter(|e| e.landed_on_zero).count() as u64, dial.zero_counter);
+        assert_eq!(dial.position, events.last().unwrap().end);
+
+        let mut dial = SafeDial::new(0, 100).unwrap();
+        let event = dial.turn(Movement::Left(250));
+        assert_eq!((event.end, event.zero_passes, event.wrapped), (50, 2, true));
+        assert!(!dial.turn(Movement::Right(0)).wrapped);
+    }
+
+    #[test]
+    fn test_notches() {
+        let mut dial = SafeDial::new(50, 100).unwrap();
+        dial.add_notch(0).unwrap();
+        dial.add_notch(82).unwrap();
+        dial.add_notch(99).unwrap();
+        assert!(dial.add_notch(100).is_err());
+        for m in parse_input(EXAMPLE).unwrap() {
+            dial.turn(m);
+        }
+
+        //A notch at 0 is counted just like the zero counters
+        assert_eq!(dial.notch(0), Some(NotchCount { landings: dial.zero_counter, passes: dial.zero_click_counter }));
+        assert_eq!(dial.notch(82), Some(NotchCount { landings: 1, passes: 4 }));
+        assert_eq!(dial.notch(99), Some(NotchCount { landings: 1, passes: 6 }));
+        assert_eq!(dial.notch(50), None);
+        assert_eq!(dial.notch_counts().len(), 3);
+    }
+
+    #[test]
+    fn test_clicks_on() {
+        assert_eq!(clicks_on(5, 10, Movement::Right(3), 8), 1);
+        assert_eq!(clicks_on(5, 10, Movement::Right(2), 8), 0);
+        assert_eq!(clicks_on(5, 10, Movement::Right(25), 5), 2);
+        assert_eq!(clicks_on(5, 10, Movement::Left(25), 5), 2);
+        assert_eq!(clicks_on(5, 10, Movement::Left(6), 9), 1);
+        assert_eq!(clicks_on(0, 10, Movement::Left(0), 0), 0);
+        assert_eq!(clicks_on(u64::MAX - 1, u64::MAX, Movement::Right(u64::MAX), 0), 1);
+        assert_eq!(clicks_on(0, 1, Movement::Left(u64::MAX), 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_huge_dials() {
+        //Bigger than an i32, which the arithmetic used to go through
+        let mut dial = SafeDial::new(2_999_999_999, 3_000_000_000).unwrap();
+        dial.turn(Movement::Right(1));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (0, 1, 1));
+        dial.turn(Movement::Left(3_000_000_001));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (2_999_999_999, 1, 2));
+
+        let mut dial = SafeDial::new(u64::MAX - 1, u64::MAX).unwrap();
+        dial.turn(Movement::Right(u64::MAX));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (u64::MAX - 1, 0, 1));
+        dial.turn(Movement::Right(1));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (0, 1, 2));
+        dial.turn(Movement::Left(u64::MAX - 1));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (1, 1, 2));
+        dial.turn(Movement::Left(2));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (u64::MAX - 1, 1, 3));
+    }
+
+    #[test]
+    fn test_huge_movements() {
+        //On a single position dial every click is onto zero
+        let mut dial = SafeDial::new(0, 1).unwrap();
+        dial.add_notch(0).unwrap();
+        let event = dial.turn(Movement::Left(u64::MAX));
+        assert_eq!((event.end, event.zero_passes, event.landed_on_zero, event.wrapped), (0, u64::MAX, true, true));
+
+        //and the counters saturate rather than overflowing
+        dial.turn(Movement::Right(5));
+        assert_eq!((dial.zero_counter, dial.zero_click_counter), (2, u64::MAX));
+        assert_eq!(dial.notch(0), Some(NotchCount { landings: 2, passes: u64::MAX }));
+
+        let mut dial = SafeDial::new(50, 100).unwrap();
+        dial.turn(Movement::Right(u64::MAX));
+        //u64::MAX ends in 15, and 50+15 doesn't get back round to zero after the last whole turn
+        assert_eq!((dial.position, dial.zero_click_counter), (65, u64::MAX / 100));
+
+        assert_eq!(parse_input("R18446744073709551615").unwrap(), vec![Movement::Right(u64::MAX)]);
+        assert!(parse_input("R18446744073709551616").is_err());
+    }
+
+    #[test]
+    fn test_clamped() {
+        let mut dial = SafeDial::with_mode(5, 10, DialMode::Clamped).unwrap();
+        dial.add_notch(0).unwrap();
+        dial.add_notch(9).unwrap();
+        let event = dial.turn(Movement::Left(3));
+        assert_eq!((event.end, event.zero_passes, event.wrapped), (2, 0, false));
+        let event = dial.turn(Movement::Left(5));
+        assert_eq!((event.end, event.zero_passes, event.landed_on_zero, event.wrapped), (0, 1, true, false));
+
+        //Pushing against the stop lands on 0 again, but doesn't click onto it
+        let event = dial.turn(Movement::Left(4));
+        assert_eq!((event.end, event.zero_passes, event.landed_on_zero), (0, 0, true));
+        dial.turn(Movement::Right(20));
+        dial.turn(Movement::Right(1));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (9, 2, 1));
+        assert_eq!(dial.notch(9), Some(NotchCount { landings: 2, passes: 1 }));
+        assert_eq!(dial.notch(0), Some(NotchCount { landings: 2, passes: 1 }));
+
+        let mut dial = SafeDial::with_mode(0, u64::MAX, DialMode::Clamped).unwrap();
+        dial.turn(Movement::Right(u64::MAX));
+        assert_eq!(dial.position, u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_detented() {
+        let mut dial = SafeDial::with_mode(0, 20, DialMode::Detented(5)).unwrap();
+        //Stopping at 3 would be between detents, so the dial carries on to 5
+        assert_eq!(dial.turn(Movement::Right(3)).end, 5);
+        let event = dial.turn(Movement::Left(7));
+        assert_eq!((event.end, event.zero_passes, event.wrapped), (15, 1, true));
+        dial.turn(Movement::Right(5));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (0, 1, 2));
+        dial.turn(Movement::Left(40));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (0, 2, 4));
+        //Carrying on to the next detent can make a whole turn
+        let event = dial.turn(Movement::Right(19));
+        assert_eq!((event.end, event.zero_passes, event.landed_on_zero), (0, 1, true));
+
+        //Rounding up to a detent mustn't overflow
+        let mut dial = SafeDial::with_mode(0, u64::MAX, DialMode::Detented(5)).unwrap();
+        let event = dial.turn(Movement::Right(u64::MAX - 1));
+        assert_eq!((event.end, event.zero_passes), (0, 1));
+    }
+
+    #[test]
+    fn test_mode_checks() {
+        assert!(SafeDial::with_mode(5, 20, DialMode::Detented(5)).is_ok());
+        assert!(SafeDial::with_mode(3, 20, DialMode::Detented(5)).is_err());
+        assert!(SafeDial::with_mode(0, 20, DialMode::Detented(3)).is_err());
+        assert!(SafeDial::with_mode(0, 20, DialMode::Detented(0)).is_err());
+        assert!(SafeDial::with_mode(0, 0, DialMode::Clamped).is_err());
+        assert!(SafeDial::with_mode(20, 20, DialMode::Wrapping).is_err());
+        assert_eq!(DialMode::Detented(5).to_string(), "detented every 5");
+    }
+
+    #[test]
+    fn test_whole_turns_from_zero() {
+        //the click that lands back on zero is one of the whole turns, so mustn't be counted again
+        let mut dial = SafeDial::new(0, 100).unwrap();
+        dial.turn(Movement::Right(200));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (0, 1, 2));
+        dial.turn(Movement::Left(0));
+        assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (0, 2, 2));
+    }
+
+    #[test]
+    fn test_new_checks() {
+        //Either of these would have underflowed on the first right turn
+        assert!(SafeDial::new(0, 0).is_err());
+        assert!(SafeDial::new(100, 100).is_err());
+        assert!(SafeDial::new(99, 100).is_ok());
+
+        let mut state = SafeDial::new(5, 10).unwrap().state();
+        state.position = 10;
+        assert!(SafeDial::from_state(state).is_err());
+    }
+}
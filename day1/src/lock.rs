@@ -0,0 +1,220 @@
+use std::{error::Error, fmt::Display};
+use common::{numbered_lines, ParseError};
+use crate::{parse_movement, DialMode, Movement, SafeDial};
+
+/**
+ * A movement of one wheel of a lock.  Wheels are numbered from 1, as they are written in the input (e.g. 2:L15)
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelMovement {
+    pub wheel: usize,
+    pub movement: Movement
+}
+
+impl Display for WheelMovement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.movement {
+            Movement::Left(n)=>write!(f, "{}:L{}", self.wheel, n),
+            Movement::Right(n)=>write!(f, "{}:R{}", self.wheel, n)
+        }
+    }
+}
+
+/**
+ * A lock made of several SafeDials, each turned with the same semantics as a lone dial.  When the wheels are coupled they
+ * behave like an odometer: each time a wheel rolls over from its last position to 0 the next wheel advances a click,
+ * and rolling back from 0 to the last position moves the next wheel back a click.  The last wheel drives nothing, and
+ * nor does a clamped wheel, as it can't roll over
+ */
+pub struct CombinationLock {
+    wheels: Vec<SafeDial>,
+    coupled: bool
+}
+
+/**
+ * How many times a dial rolls over between its last position and 0 while making the movement: once per whole turn,
+ * and once more if the rest of the movement takes it past the end
+ */
+fn rollovers(dial:&SafeDial, movement:Movement) -> u64 {
+    if dial.mode()==DialMode::Clamped {
+        return 0
+    }
+    let (position, size) = (dial.position, dial.size());
+    let (turns, steps) = dial.mode().split(movement.distance(), size);
+    let past_the_end = match movement {
+        Movement::Right(_)=>steps >= size - position,
+        Movement::Left(_)=>steps > position
+    };
+    turns + past_the_end as u64
+}
+
+impl CombinationLock {
+    pub fn new(wheels:Vec<SafeDial>, coupled:bool) -> CombinationLock {
+        CombinationLock { wheels, coupled }
+    }
+
+    pub fn wheels(&self) -> &[SafeDial] {
+        &self.wheels
+    }
+
+    /**
+     * The wheel with the given number, counting from 1
+     */
+    pub fn wheel(&self, number:usize) -> Option<&SafeDial> {
+        number.checked_sub(1).and_then(|i| self.wheels.get(i))
+    }
+
+    pub fn positions(&self) -> Vec<u64> {
+        self.wheels.iter().map(|w| w.position).collect()
+    }
+
+    pub fn zero_counts(&self) -> Vec<u64> {
+        self.wheels.iter().map(|w| w.zero_counter).collect()
+    }
+
+    pub fn zero_click_counts(&self) -> Vec<u64> {
+        self.wheels.iter().map(|w| w.zero_click_counter).collect()
+    }
+
+    /**
+     * Turns one wheel, and if the wheels are coupled, passes any roll-overs on down the line
+     */
+    pub fn turn(&mut self, wheel_movement:WheelMovement) -> Result<(), Box<dyn Error>> {
+        let mut index = wheel_movement.wheel.checked_sub(1)
+            .filter(|i| *i < self.wheels.len())
+            .ok_or_else(|| format!("there is no wheel {} on a lock of {} wheel(s)", wheel_movement.wheel, self.wheels.len()))?;
+        let mut movement = wheel_movement.movement;
+
+        loop {
+            let rolled = rollovers(&self.wheels[index], movement);
+            self.wheels[index].turn(movement);
+            if !self.coupled || rolled==0 || index+1 >= self.wheels.len() {
+                return Ok( () )
+            }
+            index += 1;
+            movement = movement.with_distance(rolled);
+        }
+    }
+}
+
+/**
+ * Parses one wheel movement per line, such as 2:L15.  A line without a wheel number turns wheel 1, so a plain
+ * list of movements for a single dial is also a valid lock input
+ */
+pub fn parse_lock_input(file_content:&str) -> Result<Vec<WheelMovement>, Box<dyn Error>> {
+    let mut results:Vec<WheelMovement> = vec![];
+
+    for (line_number, line) in numbered_lines(file_content) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let parsed = match trimmed.split_once(':') {
+            Some((wheel, movement))=>{
+                let wheel_number = wheel.parse::<usize>().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    ParseError::at(line, wheel, format!("'{}' is not a wheel number, they count up from 1", wheel))
+                });
+                wheel_number.and_then(|n| parse_movement(line, movement).map(|m| WheelMovement { wheel: n, movement: m }))
+            },
+            None=>parse_movement(line, trimmed).map(|m| WheelMovement { wheel: 1, movement: m })
+        };
+        results.push(parsed.map_err(|e| e.on_line(line_number))?);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn odometer(wheels:usize) -> CombinationLock {
+        CombinationLock::new((0..wheels).map(|_| SafeDial::new(0, 10).unwrap()).collect(), true)
+    }
+
+    fn turn_all(lock:&mut CombinationLock, input:&str) {
+        for m in parse_lock_input(input).unwrap() {
+            lock.turn(m).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let movements = parse_lock_input("2:L15\nR3\n\n10:R0\n").unwrap();
+        assert_eq!(movements, vec![
+            WheelMovement { wheel: 2, movement: Movement::Left(15) },
+            WheelMovement { wheel: 1, movement: Movement::Right(3) },
+            WheelMovement { wheel: 10, movement: Movement::Right(0) }
+        ]);
+        assert_eq!(movements[0].to_string(), "2:L15");
+
+        let e = parse_lock_input("1:L5\nx:R3\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 1: 'x' is not a wheel number, they count up from 1
+  x:R3
+  ^");
+        let e = parse_lock_input("0:L5\n").unwrap_err();
+        assert!(e.to_string().starts_with("line 1, column 1:"));
+        let e = parse_lock_input("1:L5\n2:Q5\n").unwrap_err();
+        assert!(e.to_string().starts_with("line 2, column 3: invalid direction 'Q'"));
+    }
+
+    #[test]
+    fn test_uncoupled() {
+        let mut lock = CombinationLock::new(vec![SafeDial::new(50, 100).unwrap(), SafeDial::new(5, 10).unwrap()], false);
+        turn_all(&mut lock, "1:L68\n2:R5\n1:L30\n2:R25\n1:R48");
+        assert_eq!(lock.positions(), vec![0, 5]);
+        //Each wheel counts for itself, just as a lone SafeDial would
+        assert_eq!(lock.zero_counts(), vec![1, 1]);
+        assert_eq!(lock.zero_click_counts(), vec![2, 3]);
+
+        assert!(lock.turn(WheelMovement { wheel: 3, movement: Movement::Left(1) }).is_err());
+        assert!(lock.turn(WheelMovement { wheel: 0, movement: Movement::Left(1) }).is_err());
+    }
+
+    #[test]
+    fn test_odometer() {
+        let mut lock = odometer(3);
+        turn_all(&mut lock, "R25");
+        assert_eq!(lock.positions(), vec![5, 2, 0]);
+        turn_all(&mut lock, "L6");
+        assert_eq!(lock.positions(), vec![9, 1, 0]);
+
+        //Rolling the units over carries all the way through
+        turn_all(&mut lock, "2:R8\nR1");
+        assert_eq!(lock.positions(), vec![0, 0, 1]);
+        assert_eq!(lock.wheel(2).map(|w| w.zero_click_counter), Some(1));
+
+        //and so does rolling it back
+        turn_all(&mut lock, "L1");
+        assert_eq!(lock.positions(), vec![9, 9, 0]);
+    }
+
+    #[test]
+    fn test_whole_turns_carry() {
+        let mut lock = odometer(2);
+        turn_all(&mut lock, "R30");
+        assert_eq!(lock.positions(), vec![0, 3]);
+        assert_eq!((lock.zero_counts(), lock.zero_click_counts()), (vec![1, 0], vec![3, 0]));
+
+        //The last wheel has nothing to drive, so it just wraps
+        turn_all(&mut lock, "2:R7\nR10");
+        assert_eq!(lock.positions(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_wheel_modes() {
+        //A detented wheel carries on to its next detent, and the carry comes from where it actually went
+        let detented = SafeDial::with_mode(0, 10, DialMode::Detented(5)).unwrap();
+        let mut lock = CombinationLock::new(vec![detented, SafeDial::new(0, 10).unwrap()], true);
+        turn_all(&mut lock, "R8");
+        assert_eq!(lock.positions(), vec![0, 1]);
+        turn_all(&mut lock, "L1");
+        assert_eq!(lock.positions(), vec![5, 0]);
+
+        //while a clamped wheel stops at the end and drives nothing
+        let clamped = SafeDial::with_mode(0, 10, DialMode::Clamped).unwrap();
+        let mut lock = CombinationLock::new(vec![clamped, SafeDial::new(0, 10).unwrap()], true);
+        turn_all(&mut lock, "R25\nL3");
+        assert_eq!(lock.positions(), vec![6, 0]);
+    }
+}
@@ -0,0 +1,189 @@
+use std::error::Error;
+use crate::{distance_to, DialMode, Movement, SafeDial};
+
+/**
+ * Rules some locks impose on how the combination is dialled
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Constraints {
+    /** Each movement must go the opposite way to the one before it */
+    pub alternate: bool,
+    /** Each movement must click onto 0 at least this many times on its way to the target, counted as zero_click_counter counts them */
+    pub zero_passes: u64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Left,
+    Right
+}
+
+impl Direction {
+    fn movement(&self, distance:u64) -> Movement {
+        match self {
+            Direction::Left=>Movement::Left(distance),
+            Direction::Right=>Movement::Right(distance)
+        }
+    }
+}
+
+/**
+ * The fewest clicks that get from `from` to `to` going in the given direction, while clicking onto 0 at least
+ * `zero_passes` times.  Each extra whole turn clicks onto 0 exactly once more, so we only need to work out how many
+ * the direct route manages and add turns until there are enough
+ */
+fn shortest_move(from:u64, to:u64, size:u64, direction:Direction, zero_passes:u64) -> Option<u64> {
+    let direct = if from==to { 0 } else { distance_to(from, to, size, direction.movement(0)) };
+    let direct_passes = if direct==0 {
+        0
+    } else {
+        let first_zero = distance_to(from, 0, size, direction.movement(0));
+        if direct >= first_zero { 1 } else { 0 }
+    };
+    let extra_turns = zero_passes.saturating_sub(direct_passes);
+    extra_turns.checked_mul(size)?.checked_add(direct)
+}
+
+/**
+ * Works out the movements with the fewest clicks in total that take a dial from its current position to each of the
+ * targets in order, keeping to the constraints.  When going either way costs the same, right is preferred.
+ * Only wrapping dials are supported.  The answer is checked by replaying it on a copy of the dial before it is returned
+ */
+pub fn shortest_route(dial:&SafeDial, targets:&[u64], constraints:Constraints) -> Result<Vec<Movement>, Box<dyn Error>> {
+    if dial.mode()!=DialMode::Wrapping {
+        return Err(format!("routes can only be worked out for wrapping dials, and this one is {}", dial.mode()).into())
+    }
+    let size = dial.size();
+    if let Some(t) = targets.iter().find(|t| **t >= size) {
+        return Err(format!("{} is not on a dial of size {}", t, size).into())
+    }
+
+    //best[d] is the fewest clicks so far for a route whose last movement went in direction d.  Rather than keep each
+    //route, steps[i][d] remembers the distance of movement i and which direction the movement before it went, and the
+    //cheapest route is followed back through them at the end
+    let directions = [Direction::Right, Direction::Left];
+    let mut best:[Option<u64>; 2] = [Some(0), Some(0)];
+    let mut steps:Vec<[Option<(u64, usize)>; 2]> = Vec::with_capacity(targets.len());
+    let mut position = dial.position;
+
+    for (i, target) in targets.iter().enumerate() {
+        let mut next:[Option<u64>; 2] = [None, None];
+        let mut step:[Option<(u64, usize)>; 2] = [None, None];
+        for (d, direction) in directions.iter().enumerate() {
+            let Some(distance) = shortest_move(position, *target, size, *direction, constraints.zero_passes) else {
+                continue
+            };
+            //The first movement can go either way, but after that alternating means coming from the other direction
+            let came_from:Vec<usize> = if constraints.alternate && i > 0 { vec![1 - d] } else { vec![0, 1] };
+            let cheapest = came_from.iter()
+                .filter_map(|from| best[*from].and_then(|total| total.checked_add(distance)).map(|t| (t, *from)))
+                .min_by_key(|(total, _)| *total);
+            if let Some((total, from)) = cheapest {
+                next[d] = Some(total);
+                step[d] = Some((distance, from));
+            }
+        }
+        best = next;
+        steps.push(step);
+        position = *target;
+    }
+
+    let mut last = (0..2).filter(|d| best[*d].is_some()).min_by_key(|d| best[*d])
+        .ok_or("the route is too long to count in clicks")?;
+    let mut route:Vec<Movement> = Vec::with_capacity(targets.len());
+    for step in steps.iter().rev() {
+        let (distance, from) = step[last].ok_or("the route lost its way working back from the last target")?;
+        route.push(directions[last].movement(distance));
+        last = from;
+    }
+    route.reverse();
+    check_route(dial, targets, constraints, &route)?;
+    Ok(route)
+}
+
+/**
+ * Replays the movements on a fresh copy of the dial, making sure that they stop on each target in turn and keep to the constraints
+ */
+pub fn check_route(dial:&SafeDial, targets:&[u64], constraints:Constraints, route:&[Movement]) -> Result<(), Box<dyn Error>> {
+    if route.len()!=targets.len() {
+        return Err(format!("{} movements cannot stop on {} targets", route.len(), targets.len()).into())
+    }
+    let mut replay = SafeDial::with_mode(dial.position, dial.size(), dial.mode())?;
+    let mut previous:Option<Movement> = None;
+    for (movement, target) in route.iter().zip(targets) {
+        let event = replay.turn(*movement);
+        if event.end!=*target {
+            return Err(format!("{:?} stopped at {} rather than {}", movement, event.end, target).into())
+        }
+        if event.zero_passes < constraints.zero_passes {
+            return Err(format!("{:?} only clicked onto 0 {} time(s), rather than {}", movement, event.zero_passes, constraints.zero_passes).into())
+        }
+        let same_way = matches!((previous, movement), (Some(Movement::Left(_)), Movement::Left(_))|(Some(Movement::Right(_)), Movement::Right(_)));
+        if constraints.alternate && same_way {
+            return Err(format!("{:?} went the same way as the movement before it", movement).into())
+        }
+        previous = Some(*movement);
+    }
+    Ok( () )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn route(start:u64, size:u64, targets:&[u64], constraints:Constraints) -> Vec<Movement> {
+        shortest_route(&SafeDial::new(start, size).unwrap(), targets, constraints).unwrap()
+    }
+
+    #[test]
+    fn test_unconstrained() {
+        let free = Constraints::default();
+        assert_eq!(route(50, 100, &[82, 10, 10, 90], free), vec![Movement::Right(32), Movement::Right(28), Movement::Right(0), Movement::Left(20)]);
+        //Half way round is the same either way, so we go right
+        assert_eq!(route(50, 100, &[0], free), vec![Movement::Right(50)]);
+        assert_eq!(route(50, 100, &[], free), vec![]);
+        assert!(shortest_route(&SafeDial::new(50, 100).unwrap(), &[100], free).is_err());
+        assert!(shortest_route(&SafeDial::with_mode(50, 100, DialMode::Clamped).unwrap(), &[0], free).is_err());
+    }
+
+    #[test]
+    fn test_alternating() {
+        let alternate = Constraints { alternate: true, zero_passes: 0 };
+        //R2 then L97 is 99 clicks, where L98 then R3 would be 101
+        assert_eq!(route(50, 100, &[52, 55], alternate), vec![Movement::Right(2), Movement::Left(97)]);
+        assert_eq!(route(50, 100, &[52, 55, 60], alternate), vec![Movement::Right(2), Movement::Left(97), Movement::Right(5)]);
+        assert_eq!(route(50, 100, &[48, 49], alternate), vec![Movement::Left(2), Movement::Right(1)]);
+    }
+
+    #[test]
+    fn test_zero_passes() {
+        let once = Constraints { alternate: false, zero_passes: 1 };
+        assert_eq!(route(50, 100, &[52], once), vec![Movement::Left(98)]);
+        //Landing on 0 counts as clicking onto it
+        assert_eq!(route(50, 100, &[0], once), vec![Movement::Right(50)]);
+        assert_eq!(route(50, 100, &[50], once), vec![Movement::Right(100)]);
+
+        let three_times = Constraints { alternate: true, zero_passes: 3 };
+        let movements = route(0, 40, &[20, 30, 10], three_times);
+        //Going right then left then right, the last two movements each pass 0 once on the way, so need one turn fewer
+        assert_eq!(movements, vec![Movement::Right(140), Movement::Left(110), Movement::Right(100)]);
+    }
+
+    #[test]
+    fn test_check_route() {
+        let dial = SafeDial::new(50, 100).unwrap();
+        let free = Constraints::default();
+        assert!(check_route(&dial, &[52], free, &[Movement::Right(2)]).is_ok());
+        assert!(check_route(&dial, &[52], free, &[Movement::Right(3)]).is_err());
+        assert!(check_route(&dial, &[52, 53], free, &[Movement::Right(2)]).is_err());
+        assert!(check_route(&dial, &[52, 53], Constraints { alternate: true, zero_passes: 0 }, &[Movement::Right(2), Movement::Right(1)]).is_err());
+        assert!(check_route(&dial, &[52], Constraints { alternate: false, zero_passes: 1 }, &[Movement::Right(2)]).is_err());
+    }
+
+    #[test]
+    fn test_huge_dial() {
+        let movements = route(1, u64::MAX, &[u64::MAX - 1], Constraints::default());
+        assert_eq!(movements, vec![Movement::Left(2)]);
+        assert!(shortest_route(&SafeDial::new(1, u64::MAX).unwrap(), &[0], Constraints { alternate: false, zero_passes: 3 }).is_err());
+    }
+}
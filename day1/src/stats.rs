@@ -0,0 +1,115 @@
+use std::{collections::BTreeMap, ops::Range};
+use crate::{Movement, SafeDial, TurnEvent};
+
+/**
+ * A summary of a movement log, built from the events of turning a dial through it
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DialStats {
+    pub movements: usize,
+    /** How many movements finished on each position.  Positions that were never landed on are left out */
+    pub landings: BTreeMap<u64, u64>,
+    /**
+     * The indices of the longest run of movements that neither clicked onto 0 nor finished there.  If there are
+     * several of the same length this is the first
+     */
+    pub longest_without_zero: Range<usize>,
+    /** The clicks the dial actually travelled to the left, which stops and detents can make differ from the log */
+    pub left_clicks: u64,
+    pub right_clicks: u64,
+    /** How many movements there were of each size, whichever way they went */
+    pub movement_sizes: BTreeMap<u64, u64>
+}
+
+impl DialStats {
+    pub fn from_events(events:impl IntoIterator<Item = TurnEvent>) -> DialStats {
+        let mut stats = DialStats::default();
+        let mut streak_start = 0;
+
+        for (i, event) in events.into_iter().enumerate() {
+            stats.movements += 1;
+            *stats.landings.entry(event.end).or_default() += 1;
+            *stats.movement_sizes.entry(event.movement.distance()).or_default() += 1;
+            match event.movement {
+                Movement::Left(_)=>stats.left_clicks = stats.left_clicks.saturating_add(event.clicks),
+                Movement::Right(_)=>stats.right_clicks = stats.right_clicks.saturating_add(event.clicks)
+            }
+
+            if event.zero_passes > 0 || event.landed_on_zero {
+                streak_start = i + 1;
+            } else if i + 1 - streak_start > stats.longest_without_zero.len() {
+                stats.longest_without_zero = streak_start..i + 1;
+            }
+        }
+        stats
+    }
+
+    /**
+     * Turns the dial through the movements, collecting the statistics as it goes
+     */
+    pub fn collect(dial:&mut SafeDial, movements:&[Movement]) -> DialStats {
+        DialStats::from_events(dial.events(movements.iter().copied()))
+    }
+
+    /**
+     * Writes the statistics as CSV, one value per row, so that every kind of statistic fits in the same three columns.
+     * The streak is given by the index of its first movement and its length
+     */
+    pub fn to_csv(&self) -> String {
+        let mut rows:Vec<String> = vec!["statistic,key,value".to_string()];
+        rows.push(format!("movements,,{}", self.movements));
+        rows.push(format!("clicks,left,{}", self.left_clicks));
+        rows.push(format!("clicks,right,{}", self.right_clicks));
+        rows.push(format!("longest_without_zero,start,{}", self.longest_without_zero.start));
+        rows.push(format!("longest_without_zero,length,{}", self.longest_without_zero.len()));
+        rows.extend(self.landings.iter().map(|(position, count)| format!("landings,{},{}", position, count)));
+        rows.extend(self.movement_sizes.iter().map(|(size, count)| format!("movement_size,{},{}", size, count)));
+        rows.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::assert_snapshot;
+    use crate::{parse_input, DialMode, EXAMPLE};
+
+    fn log_stats(start:u64, size:u64, input:&str) -> DialStats {
+        DialStats::collect(&mut SafeDial::new(start, size).unwrap(), &parse_input(input).unwrap())
+    }
+
+    #[test]
+    fn test_example() {
+        let stats = log_stats(50, 100, EXAMPLE);
+        assert_eq!(stats.movements, 10);
+        assert_eq!((stats.left_clicks, stats.right_clicks), (340, 122));
+        assert_eq!(stats.landings.get(&0), Some(&3));
+        assert_eq!(stats.landings.values().sum::<u64>(), 10);
+        //Every other movement or so touches zero, so the first lone movement that doesn't is the longest run
+        assert_eq!(stats.longest_without_zero, 1..2);
+        assert_snapshot!("example_stats", stats.to_csv());
+    }
+
+    #[test]
+    fn test_travelled_clicks() {
+        //The stops cut the first two movements short, and the detents round the last two up
+        let movements = parse_input("L8\nR30\nL2\nR6\n").unwrap();
+        let stats = DialStats::collect(&mut SafeDial::with_mode(5, 20, DialMode::Clamped).unwrap(), &movements[..2]);
+        assert_eq!((stats.left_clicks, stats.right_clicks), (5, 19));
+        let stats = DialStats::collect(&mut SafeDial::with_mode(5, 20, DialMode::Detented(5)).unwrap(), &movements[2..]);
+        assert_eq!((stats.left_clicks, stats.right_clicks), (5, 10));
+    }
+
+    #[test]
+    fn test_streaks() {
+        let stats = log_stats(0, 10, "R1\nR1\nR8\nL1\nL2\nR2\nL5\nR2\nR4\n");
+        //Leaving 0 doesn't touch it, so the streak starts straight after landing there
+        assert_eq!(stats.longest_without_zero, 3..8);
+        assert_eq!(stats.movement_sizes, BTreeMap::from([(1, 3), (2, 3), (4, 1), (5, 1), (8, 1)]));
+
+        //Standing still on zero still touches it
+        assert_eq!(log_stats(0, 10, "L0\nR0\n").longest_without_zero, 0..0);
+        assert_eq!(log_stats(5, 10, "R1\nL2\n").longest_without_zero, 0..2);
+        assert_eq!(log_stats(5, 10, ""), DialStats::default());
+    }
+}
@@ -0,0 +1,206 @@
+use common::Puzzle;
+use day1::{fold, generate, solver::{self, Constraints}, Day1, DialMode, Movement, NotchCount, SafeDial};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/**
+ * Turns the dial one click at a time, counting how often it ends a movement on the notch and how many clicks land on it
+ */
+fn brute_force(movements:&[Movement], notch:i64) -> (u64, u64) {
+    let mut position:i64 = 50;
+    let mut landings = 0;
+    let mut clicks = 0;
+    for m in movements {
+        let (step, count) = match m {
+            Movement::Left(n)=>(-1, *n),
+            Movement::Right(n)=>(1, *n)
+        };
+        for _ in 0..count {
+            position = (position + step).rem_euclid(100);
+            if position==notch {
+                clicks += 1;
+            }
+        }
+        if position==notch {
+            landings += 1;
+        }
+    }
+    (landings, clicks)
+}
+
+proptest! {
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, 40);
+        let movements = Day1.parse(&input).unwrap();
+
+        let (landings, clicks) = brute_force(&movements, 0);
+        prop_assert_eq!(Day1.part1(&movements).unwrap(), landings, "input:\n{}", input);
+        prop_assert_eq!(Day1.part2(&movements).unwrap(), clicks, "input:\n{}", input);
+    }
+}
+
+proptest! {
+    #[test]
+    fn notches_match_brute_force(seed in any::<u64>(), notches in prop::collection::vec(0_u64..100, 1..5)) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let movements = generate::movements(&mut rng, 40, 400);
+
+        let mut dial = SafeDial::new(50, 100).unwrap();
+        for notch in notches.iter() {
+            dial.add_notch(*notch).unwrap();
+        }
+        for m in movements.iter() {
+            dial.turn(*m);
+        }
+        for notch in notches {
+            let (landings, passes) = brute_force(&movements, notch as i64);
+            prop_assert_eq!(dial.notch(notch), Some(NotchCount { landings, passes }), "notch {} for {:?}", notch, movements);
+        }
+    }
+}
+
+/**
+ * The fewest clicks to dial the targets, found by trying every movement of up to a few turns each way, one click at a
+ * time, and keeping the cheapest total for each direction the last movement could have gone
+ */
+fn brute_force_route(start:u64, size:u64, targets:&[u64], constraints:Constraints) -> Option<u64> {
+    let mut best:[Option<u64>; 2] = [Some(0), Some(0)];
+    let mut position = start;
+    for (i, target) in targets.iter().enumerate() {
+        let mut next:[Option<u64>; 2] = [None, None];
+        for (d, step) in [1_i64, -1].iter().enumerate() {
+            let (mut at, mut zero_clicks) = (position as i64, 0);
+            let mut cheapest = if position==*target && constraints.zero_passes==0 { Some(0) } else { None };
+            for clicks in 1..=(constraints.zero_passes + 2) * size {
+                at = (at + step).rem_euclid(size as i64);
+                if at==0 {
+                    zero_clicks += 1;
+                }
+                if cheapest.is_none() && at==*target as i64 && zero_clicks >= constraints.zero_passes {
+                    cheapest = Some(clicks);
+                }
+            }
+            let came_from = if constraints.alternate && i > 0 { vec![1 - d] } else { vec![0, 1] };
+            next[d] = cheapest.and_then(|c| came_from.iter().filter_map(|from| best[*from]).min().map(|total| total + c));
+        }
+        best = next;
+        position = *target;
+    }
+    best.into_iter().flatten().min()
+}
+
+proptest! {
+    #[test]
+    fn shortest_route_matches_brute_force(start in 0_u64..8, size in 1_u64..9, targets in prop::collection::vec(0_u64..8, 0..4),
+            alternate in any::<bool>(), zero_passes in 0_u64..3) {
+        let (start, targets):(u64, Vec<u64>) = (start % size, targets.into_iter().map(|t| t % size).collect());
+        let constraints = Constraints { alternate, zero_passes };
+        let route = solver::shortest_route(&SafeDial::new(start, size).unwrap(), &targets, constraints).unwrap();
+
+        let total:u64 = route.iter().map(|m| m.distance()).sum();
+        prop_assert_eq!(Some(total), brute_force_route(start, size, &targets, constraints), "route {:?}", route);
+    }
+}
+
+/**
+ * Turns a dial of any mode one click at a time, returning where it ends up and how it touched the notch, as
+ * (position, landings, passes).  A clamped dial doesn't click at all against a stop, and a detented one keeps going
+ * after the movement until it reaches a detent
+ */
+fn brute_force_mode(start:u64, size:u64, mode:DialMode, movements:&[Movement], notch:u64) -> (u64, u64, u64) {
+    let (mut position, mut landings, mut passes) = (start as i64, 0, 0);
+    let size = size as i64;
+    for m in movements {
+        let (step, count) = match m {
+            Movement::Left(n)=>(-1, *n),
+            Movement::Right(n)=>(1, *n)
+        };
+        let mut clicks = 0;
+        loop {
+            let settled = match mode {
+                DialMode::Detented(k)=>position % k as i64==0,
+                _=>true
+            };
+            if clicks >= count && settled {
+                break;
+            }
+            clicks += 1;
+            let next = position + step;
+            if mode==DialMode::Clamped && (next < 0 || next >= size) {
+                continue;
+            }
+            position = next.rem_euclid(size);
+            if position==notch as i64 {
+                passes += 1;
+            }
+        }
+        if position==notch as i64 {
+            landings += 1;
+        }
+    }
+    (position as u64, landings, passes)
+}
+
+proptest! {
+    #[test]
+    fn modes_match_brute_force(seed in any::<u64>(), mode in 0_u8..3, detent in 1_u64..6, detents in 1_u64..10, start in any::<u64>(), notch in any::<u64>()) {
+        let size = detent * detents;
+        let mode = match mode {
+            0=>DialMode::Wrapping,
+            1=>DialMode::Clamped,
+            _=>DialMode::Detented(detent)
+        };
+        let start = match mode {
+            DialMode::Detented(k)=>start % size / k * k,
+            _=>start % size
+        };
+        let notch = notch % size;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let movements = generate::movements(&mut rng, 20, 100);
+
+        let mut dial = SafeDial::with_mode(start, size, mode).unwrap();
+        dial.add_notch(0).unwrap();
+        dial.add_notch(notch).unwrap();
+        for m in movements.iter() {
+            dial.turn(*m);
+        }
+        //The zero counters count exactly as a notch at 0 does
+        let (position, landings, passes) = brute_force_mode(start, size, mode, &movements, 0);
+        prop_assert_eq!((dial.position, dial.zero_counter, dial.zero_click_counter), (position, landings, passes), "{} dial of size {} from {}: {:?}", mode, size, start, movements);
+        let (_, landings, passes) = brute_force_mode(start, size, mode, &movements, notch);
+        prop_assert_eq!(dial.notch(notch), Some(NotchCount { landings, passes }), "notch {} on {} dial of size {} from {}: {:?}", notch, mode, size, start, movements);
+    }
+}
+
+proptest! {
+    #[test]
+    fn fold_matches_turning(start in any::<u64>(), size in prop_oneof![1_u64..200, any::<u64>().prop_map(|s| s.max(1))], detent in 0_u64..4,
+            movements in prop::collection::vec((any::<bool>(), prop_oneof![0_u64..500, any::<u64>()]), 0..60)) {
+        //Only detents that fit round the dial, with 0 meaning none at all
+        let mode = match detent {
+            0=>DialMode::Wrapping,
+            k if size.is_multiple_of(k)=>DialMode::Detented(k),
+            _=>DialMode::Wrapping
+        };
+        let start = match mode {
+            DialMode::Detented(k)=>start % size / k * k,
+            _=>start % size
+        };
+        let movements:Vec<Movement> = movements.into_iter().map(|(left, n)| if left { Movement::Left(n) } else { Movement::Right(n) }).collect();
+
+        let dial = SafeDial::with_mode(start, size, mode).unwrap();
+        let mut turned = SafeDial::with_mode(start, size, mode).unwrap();
+        for m in movements.iter() {
+            turned.turn(*m);
+        }
+        prop_assert_eq!(fold::evaluate(&dial, &movements).unwrap(), turned.state(), "{} dial of size {} from {}: {:?}", mode, size, start, movements);
+    }
+}
+
+#[test]
+fn brute_force_matches_example() {
+    let movements = Day1.parse(day1::EXAMPLE).unwrap();
+    assert_eq!(brute_force(&movements, 0), (3, 6));
+}
@@ -0,0 +1,19 @@
+use rand::Rng;
+
+/**
+ * A random list of ID ranges, written the way the puzzle input writes them: `start-end`, comma separated.
+ * Some ranges start on a repeated ID, to make sure there are a few in each input.  IDs are at most 12 digits long
+ */
+pub fn input(rng:&mut impl Rng, max_ranges:usize, max_len:u64) -> String {
+    let count = rng.random_range(1..=max_ranges);
+    let ranges:Vec<String> = (0..count).map(|_| {
+        let start = if rng.random_bool(0.3) {
+            let half = rng.random_range(1..10_000u64).to_string();
+            half.repeat(rng.random_range(2..=3)).parse::<u64>().unwrap()
+        } else {
+            rng.random_range(1..100_000_000u64)
+        };
+        format!("{}-{}", start, start + rng.random_range(0..=max_len))
+    }).collect();
+    ranges.join(",") + "\n"
+}
@@ -0,0 +1,259 @@
+use std::error::Error;
+use regex::Regex;
+use common::{json, numbered_lines, trace, Example, ParseError, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Debug)]
+pub struct ProductIdRange {
+    start: u64,
+    end: u64
+}
+
+fn split_into_n_segments(s: &str, n: usize) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let len = chars.len();
+    let mut segments = Vec::with_capacity(n);
+
+    let mut start = 0;
+    let base_size = len / n;
+    let remainder = len % n;
+
+    for i in 0..n {
+        let extra = if i < remainder { 1 } else { 0 };
+        let end = start + base_size + extra;
+        segments.push(chars[start..end].iter().collect());
+        start = end;
+    }
+
+    segments
+}
+
+impl ProductIdRange {
+        /**
+         * OK. The instructions say:
+         * Since the young Elf was just doing silly patterns, you can find the invalid IDs by looking for any ID which is made only 
+         * of some sequence of digits repeated twice. So, 55 (5 twice), 6464 (64 twice), and 123123 (123 twice) would all be invalid IDs.
+        */
+    fn is_borken(id: &u64) -> bool {
+        let id_str= id.to_string();
+
+        let len = id_str.len();
+        if len <2 { //we can't get a repeating pattern if it is not long enough
+            false
+        } else {
+            for chunk_count in 2..len+1 {
+                //Test the ID.  We start by splitting in half, then checking if the two halves are equal to each other.
+                //If so we return true; if not, we reduce the half-length and try again.
+                //We keep going until we find a point at which all splits are equal or we run out of string
+                let parts= split_into_n_segments(&id_str, chunk_count);
+                let matches = match parts.first() {
+                    Some(first) if *first != id_str=>parts.iter().all(|ent| ent==first),
+                    _=>false
+                };
+                if matches {
+                    trace!(id, chunk_count, "ID is made of a repeated pattern");
+                    return true
+                }
+            }
+            false
+        }
+    }
+
+    /**
+     * The stricter rule from the first part of the puzzle: the ID must be made of a sequence of digits
+     * repeated _exactly_ twice, so 1010 is invalid but 111 is not
+     */
+    fn is_doubled(id: &u64) -> bool {
+        let id_str = id.to_string();
+        let len = id_str.len();
+        len.is_multiple_of(2) && id_str[0..len/2] == id_str[len/2..]
+    }
+
+    pub fn find_broken_ids(&self) -> Vec<u64> {
+        (self.start..self.end)
+            .filter(ProductIdRange::is_borken)
+            .collect()
+    }
+
+    pub fn find_doubled_ids(&self) -> Vec<u64> {
+        (self.start..self.end)
+            .filter(ProductIdRange::is_doubled)
+            .collect()
+    }
+
+    pub fn from_string(input: &str) -> Result<ProductIdRange, Box<dyn Error>> {
+        Ok(ProductIdRange::from_part(input, input)?)
+    }
+
+    /**
+     * Parses `part`, which is one range sliced out of `line`, so that errors can point at where it is in the line
+     */
+    fn from_part(line: &str, part: &str) -> Result<ProductIdRange, ParseError> {
+        let splitter = Regex::new(r"(\d+)-(\d+)").unwrap();
+        let number = |s: &str| s.parse::<u64>().map_err(|e| ParseError::at(line, s, format!("'{}' is not a valid ID: {}", s, e)));
+        match splitter.captures(part).map(|c| c.extract()) {
+            Some((_, [start_str, end_str]))=>{
+                let start = number(start_str)?;
+                let end = number(end_str)? + 1;
+                Ok(ProductIdRange { start, end })
+            },
+            None=>Err(ParseError::at(line, part.trim(), format!("'{}' is not a range like 11-22", part.trim())))
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<ProductIdRange>, Box<dyn Error>> {
+    let mut ranges = vec![];
+    for (line_number, line) in numbered_lines(input) {
+        for part in line.split(",").filter(|part| !part.trim().is_empty()) {
+            ranges.push(ProductIdRange::from_part(line, part).map_err(|e| e.on_line(line_number))?);
+        }
+    }
+    Ok(ranges)
+}
+
+/**
+ * The worked example of ID ranges from the puzzle text
+ */
+pub const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124
+";
+
+/**
+ * Part 1 sums the IDs made of a sequence repeated exactly twice, part 2 those repeated any number of times
+ */
+#[derive(Default)]
+pub struct Day2;
+
+impl Puzzle for Day2 {
+    type Input = Vec<ProductIdRange>;
+    type Output = u64;
+
+    fn parse(&self, input:&str) -> Result<Vec<ProductIdRange>, Box<dyn Error>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input:&Vec<ProductIdRange>) -> Result<u64, Box<dyn Error>> {
+        Ok(input.iter().flat_map(|r| r.find_doubled_ids()).sum())
+    }
+
+    fn part2(&self, input:&Vec<ProductIdRange>) -> Result<u64, Box<dyn Error>> {
+        Ok(input.iter().flat_map(|r| r.find_broken_ids()).sum())
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "1227775554", part2: "4174379265" }
+    }
+
+    fn details(&self, input:&Vec<ProductIdRange>) -> Result<Value, Box<dyn Error>> {
+        Ok(json!({
+            "ranges": input.len(),
+            "doubled_ids": input.iter().map(|r| r.find_doubled_ids().len()).sum::<usize>(),
+            "broken_ids": input.iter().map(|r| r.find_broken_ids().len()).sum::<usize>()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_broken_id_123123() {
+        let range = ProductIdRange { start: 123123, end: 123124 };
+        let ids = range.find_broken_ids();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0], 123123);
+    }
+
+    #[test]
+    fn test_broken_id_6464() {
+        let range = ProductIdRange { start: 6464, end: 6465 };
+        let ids = range.find_broken_ids();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0], 6464);
+    }
+
+    #[test]
+    fn test_broken_id_55() {
+        let range = ProductIdRange { start: 55, end: 56 };
+        let ids = range.find_broken_ids();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0], 55);
+    }
+
+    #[test]
+    fn test_working_id_7654() {
+        let range = ProductIdRange { start: 7654, end: 7655 };
+        let ids = range.find_broken_ids();
+        assert_eq!(ids.len(), 0);
+    }
+
+    #[test]
+    fn test_doubled_ids() {
+        let range = ProductIdRange::from_string("95-115").unwrap();
+        assert_eq!(range.find_doubled_ids(), vec![99]);
+        let range = ProductIdRange::from_string("998-1012").unwrap();
+        assert_eq!(range.find_doubled_ids(), vec![1010]);
+        let range = ProductIdRange::from_string("2121212118-2121212124").unwrap();
+        assert_eq!(range.find_doubled_ids(), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input("11-22,95-115\n").unwrap().len(), 2);
+
+        let e = parse_input("11-22,95_115,998-1012").unwrap_err();
+        assert_eq!(e.to_string(), "line 1, column 7: '95_115' is not a range like 11-22
+  11-22,95_115,998-1012
+        ^^^^^^");
+        let e = parse_input("11-22\n1-99999999999999999999").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column, e.width), (2, 3, 20));
+    }
+
+    #[test]
+    fn test_parser_inclusive() {
+        //The ranges we are given are inclusive, but the domain object range is exclusive.  Therefore end must be 1 more than the end value given
+        let r = ProductIdRange::from_string("222220-222224").unwrap();
+        assert_eq!(r.start, 222220);
+        assert_eq!(r.end, 222225);
+    }
+    #[test]
+    fn test_example() {
+        /*
+        11-22 has two invalid IDs, 11 and 22.
+        95-115 has one invalid ID, 99.
+        998-1012 has one invalid ID, 1010.
+        1188511880-1188511890 has one invalid ID, 1188511885.
+        222220-222224 has one invalid ID, 222222.
+        1698522-1698528 contains no invalid IDs.
+        446443-446449 has one invalid ID, 446446.
+        38593856-38593862 has one invalid ID, 38593859.
+        */
+        let ranges:Vec<ProductIdRange> = ["11-22","95-115","998-1012","1188511880-1188511890","222220-222224",
+            "1698522-1698528","446443-446449","38593856-38593862","565653-565659","824824821-824824827","2121212118-2121212124"]
+            .into_iter()
+            .map(|s| ProductIdRange::from_string(s))
+            .map(|result| result.unwrap())  //meh, we can crash a test :D
+            .collect();
+
+        assert_eq!(ranges[0].find_broken_ids(), vec![11,22]);
+        assert_eq!(ranges[1].find_broken_ids(), vec![99, 111]);
+        assert_eq!(ranges[2].find_broken_ids(), vec![999, 1010]);
+        assert_eq!(ranges[3].find_broken_ids(), vec![1188511885]);
+        assert_eq!(ranges[4].find_broken_ids(), vec![222222]);
+        assert_eq!(ranges[5].find_broken_ids(), Vec::<u64>::new());
+        assert_eq!(ranges[6].find_broken_ids(), vec![446446]);
+        assert_eq!(ranges[7].find_broken_ids(), vec![38593859]);
+        assert_eq!(ranges[8].find_broken_ids(), vec![565656]);
+        assert_eq!(ranges[9].find_broken_ids(), vec![824824824]);
+        assert_eq!(ranges[10].find_broken_ids(), vec![2121212121]);
+
+        let sum = ranges.into_iter()
+            .flat_map(|r| r.find_broken_ids())
+            .reduce(|sum, elem| sum+elem);
+
+        assert_eq!(sum, Some(4174379265));
+    }
+}
\ No newline at end of file
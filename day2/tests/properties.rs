@@ -0,0 +1,35 @@
+use common::Puzzle;
+use day2::{generate, Day2};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/**
+ * Checks an ID by trying every way of cutting it into equal-length pieces
+ */
+fn repeats(id:u64, only_twice:bool) -> bool {
+    let s = id.to_string();
+    let counts:Vec<usize> = if only_twice { vec![2] } else { (2..=s.len()).collect() };
+    counts.into_iter().any(|count| s.len().is_multiple_of(count) && s[0..s.len()/count].repeat(count) == s)
+}
+
+fn brute_force(input:&str, only_twice:bool) -> u64 {
+    input.trim().split(",").map(|range| {
+        let (start, end) = range.split_once("-").unwrap();
+        (start.parse::<u64>().unwrap()..=end.parse::<u64>().unwrap()).filter(|id| repeats(*id, only_twice)).sum::<u64>()
+    }).sum()
+}
+
+proptest! {
+    //the optimised solver is slow without optimisations, so keep the runs small
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, 4, 500);
+        let ranges = Day2.parse(&input).unwrap();
+
+        prop_assert_eq!(Day2.part1(&ranges).unwrap(), brute_force(&input, true), "input: {}", input);
+        prop_assert_eq!(Day2.part2(&ranges).unwrap(), brute_force(&input, false), "input: {}", input);
+    }
+}
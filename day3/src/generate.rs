@@ -0,0 +1,14 @@
+use rand::Rng;
+
+/**
+ * A random set of battery banks, one per line, with joltages from 1 to 9.  Every bank has at least 12 batteries, as
+ * part 2 needs that many
+ */
+pub fn input(rng:&mut impl Rng, max_banks:usize, max_len:usize) -> String {
+    let count = rng.random_range(1..=max_banks);
+    (0..count).map(|_| {
+        let len = rng.random_range(12..=max_len.max(12));
+        let bank:String = (0..len).map(|_| char::from_digit(rng.random_range(1..=9), 10).unwrap()).collect();
+        bank + "\n"
+    }).collect()
+}
@@ -0,0 +1,200 @@
+use std::error::Error;
+use common::{json, Example, ParseError, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Debug)]
+pub struct BatteryBank {
+    content: Vec<u32>
+}
+
+impl BatteryBank {
+    pub fn from_string(input:&str) -> Result<BatteryBank, Box<dyn Error>> {
+        Ok(BatteryBank::from_line(input)?)
+    }
+
+    fn from_line(input:&str) -> Result<BatteryBank, ParseError> {
+        let content:Result<Vec<u32>, ParseError> = input.char_indices().map(|(i, ch)| {
+            ch.to_digit(10).ok_or_else(|| {
+                ParseError::at(input, &input[i..i+ch.len_utf8()], format!("'{}' is not a battery joltage (0-9)", ch))
+            })
+        }).collect();
+        content.map(|content| BatteryBank { content })
+    }
+
+    /**
+     * Calculate the "joltage" (defined in the problem) for the battery bank.
+     * This is the highest two-numbered value that can be gained by taking one
+     * digit as the tens and another to the right of it as the ones
+     */
+    pub fn max_joltage(&self) -> u32 {
+        //Step one - where is the highest digit
+        let mut highest_index = 0;
+        let mut highest_val = 0;
+        if self.content.len() < 2 { //can't make a two-digit number if we have less than 2 digits to start with!
+            return 0;   
+        }
+        for i in 0..(self.content.len()-1) {    //-1, because we can't make a two-digit number in order from the last number in the set
+            if self.content[i] > highest_val {
+                highest_index = i;
+                highest_val = self.content[i];
+            }
+        }
+        
+        //Step two - find the next highest digit after that
+        let mut second_highest = 0;
+        for i in (highest_index+1)..self.content.len() {
+            if self.content[i] > second_highest {
+                second_highest = self.content[i];
+            }
+        }
+
+        (highest_val * 10) + second_highest
+    }
+
+    /**
+     * Calculate the "joltage" (defined in the problem) for the battery bank.
+     * This is now the highest 12-numbered value that can be gained by taking one
+     * digit as the 10^x and another to the right of it as the 10^(x-1) etc.
+     */
+    pub fn max_joltage_v2(&self) -> u64 {
+        fn find_next_highest(content: &[u32], pow:usize,start_pos:usize) -> (u64, usize) {
+            //If we are e.g. at power 10, there must be at least 10 other values following us in order to be valid.
+            //Practically this means we cut off at len - 10
+            if content.is_empty() {
+                return (0, 0)
+            }
+            let mut highest:u64 = 0;
+            let mut highest_index:usize = 0;
+            for (i, value) in content.iter().enumerate().take(content.len() - pow).skip(start_pos) {
+                if u64::from(*value) > highest {
+                    highest = (*value).into();
+                    highest_index = i;
+                }
+            }
+
+            (highest * 10_u64.pow(pow.try_into().unwrap()), highest_index+1)    //next start position is current highest index + 1
+        }
+
+        let mut sum:u64 = 0;
+        let mut pos:usize = 0;
+        for pow in (0..12).rev() {
+            let (next_value, next_pos) = find_next_highest(&self.content, pow, pos);
+            sum += next_value;
+            pos = next_pos;
+        }
+
+        sum
+    }
+}
+
+pub fn parse_input(content:&str) -> Result<Vec<BatteryBank>, Box<dyn Error>> {
+    content
+        .split("\n")
+        .enumerate()
+        .map(|(i, s)| BatteryBank::from_line(s).map_err(|e| e.on_line(i+1).into()))
+        .collect()
+}
+
+/**
+ * The worked example of battery banks from the puzzle text
+ */
+pub const EXAMPLE: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+/**
+ * Part 1 sums the two-digit joltage of every bank, part 2 the twelve-digit joltage
+ */
+#[derive(Default)]
+pub struct Day3;
+
+impl Puzzle for Day3 {
+    type Input = Vec<BatteryBank>;
+    type Output = u64;
+
+    fn parse(&self, input:&str) -> Result<Vec<BatteryBank>, Box<dyn Error>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input:&Vec<BatteryBank>) -> Result<u64, Box<dyn Error>> {
+        Ok(input.iter().map(|b| u64::from(b.max_joltage())).sum())
+    }
+
+    fn part2(&self, input:&Vec<BatteryBank>) -> Result<u64, Box<dyn Error>> {
+        Ok(input.iter().map(|b| b.max_joltage_v2()).sum())
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "357", part2: "3121910778619" }
+    }
+
+    fn details(&self, input:&Vec<BatteryBank>) -> Result<Value, Box<dyn Error>> {
+        Ok(json!({ "banks": input.len() }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example1() {
+        let bank = BatteryBank::from_string("987654321111111").unwrap();
+        assert_eq!(bank.max_joltage(), 98);
+    }
+
+    #[test]
+    fn test_example2() {
+        let bank = BatteryBank::from_string("811111111111119").unwrap();
+        assert_eq!(bank.max_joltage(), 89);
+    }
+
+    #[test]
+    fn test_example3() {
+        let bank = BatteryBank::from_string("234234234234278").unwrap();
+        assert_eq!(bank.max_joltage(), 78);
+    }
+
+    #[test]
+    fn test_example4() {
+        let bank = BatteryBank::from_string("818181911112111").unwrap();
+        assert_eq!(bank.max_joltage(), 92);
+    }
+
+    #[test]
+    fn test_example1v2() {
+        let bank = BatteryBank::from_string("987654321111111").unwrap();
+        assert_eq!(bank.max_joltage_v2(), 987654321111);
+    }
+
+    #[test]
+    fn test_example2v2() {
+        let bank = BatteryBank::from_string("811111111111119").unwrap();
+        assert_eq!(bank.max_joltage_v2(), 811111111119);
+    }
+
+
+    #[test]
+    fn test_example2v3() {
+        let bank = BatteryBank::from_string("234234234234278").unwrap();
+        assert_eq!(bank.max_joltage_v2(), 434234234278);
+    }
+
+
+    #[test]
+    fn test_example2v4() {
+        let bank = BatteryBank::from_string("818181911112111").unwrap();
+        assert_eq!(bank.max_joltage_v2(), 888911112111);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_input("987654321111111\n8111a1111111119").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 5: 'a' is not a battery joltage (0-9)
+  8111a1111111119
+      ^");
+    }
+}
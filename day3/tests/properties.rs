@@ -0,0 +1,38 @@
+use common::Puzzle;
+use day3::{generate, Day3};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/**
+ * The largest number we can make by picking `count` digits from the bank in order, trying every possible choice
+ */
+fn best(digits:&[u64], count:usize) -> Option<u64> {
+    if count==0 {
+        return Some(0)
+    }
+    (0..digits.len()).filter_map(|i| {
+        best(&digits[i+1..], count-1).map(|rest| digits[i] * 10_u64.pow((count-1) as u32) + rest)
+    }).max()
+}
+
+fn brute_force(input:&str, count:usize) -> u64 {
+    input.lines().map(|bank| {
+        let digits:Vec<u64> = bank.chars().map(|ch| ch.to_digit(10).unwrap().into()).collect();
+        best(&digits, count).unwrap()
+    }).sum()
+}
+
+proptest! {
+    //trying every choice of 12 digits is slow, so keep the runs small
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, 4, 15);
+        let banks = Day3.parse(&input).unwrap();
+
+        prop_assert_eq!(Day3.part1(&banks).unwrap(), brute_force(&input, 2), "input:\n{}", input);
+        prop_assert_eq!(Day3.part2(&banks).unwrap(), brute_force(&input, 12), "input:\n{}", input);
+    }
+}
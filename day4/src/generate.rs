@@ -0,0 +1,11 @@
+use rand::Rng;
+
+/**
+ * A random warehouse floor of the given size, with roughly `density` of the slots holding a roll of paper
+ */
+pub fn input(rng:&mut impl Rng, width:usize, height:usize, density:f64) -> String {
+    (0..height).map(|_| {
+        let row:String = (0..width).map(|_| if rng.random_bool(density) { '@' } else { '.' }).collect();
+        row + "\n"
+    }).collect()
+}
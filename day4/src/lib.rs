@@ -0,0 +1,335 @@
+use std::error::Error;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use common::{debug, json, Example, Grid, Neighbourhood, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Slot {
+    Empty,
+    Occupied
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SlotMobility {
+    Empty,
+    Accessible,
+    Immovable
+}
+
+#[derive(Clone)]
+pub struct WarehouseGrid {
+    contents: Grid<Slot>
+}
+
+pub struct WarehouseAvailability {
+    contents: Grid<SlotMobility>
+}
+
+impl Slot {
+    pub fn to_char(&self) -> char {
+        match self {
+            Slot::Empty=>'.',
+            Slot::Occupied=>'@'
+        }
+    }
+}
+
+impl SlotMobility {
+    pub fn to_char(&self) -> char {
+        match self {
+            SlotMobility::Empty=>'.',
+            SlotMobility::Accessible=>'x',
+            SlotMobility::Immovable=>'@'
+        }
+    }
+}
+
+impl WarehouseGrid {
+    /**
+     * Populates a WarehouseGrid from string input.  This should be a 2d array of chars like this:
+     *  ..@@.@@@@.
+        @@@.@.@.@@
+        @@@@@.@.@@
+        @.@@@@..@.
+        @@.@@@@.@@
+        .@@@@@@@.@
+        .@.@.@.@@@
+        @.@@@.@@@@
+        .@@@@@@@@.
+        @.@.@@@.@.
+     * . represents an empty slot and @ represents an occupied slot.
+     * Any invalid characters will result in a parsing error
+     */
+    pub fn from_string(input:&str) -> Result<WarehouseGrid, Box<dyn Error>> {
+        let contents = Grid::parse(input, |ch| match ch {
+            '.'=>Ok(Slot::Empty),
+            '@'=>Ok(Slot::Occupied),
+            other=>Err(format!("Unparseable character '{}'", other))
+        })?;
+        Ok(WarehouseGrid { contents })
+    }
+
+    pub fn at(&self, row:i32, col:i32) -> Option<Slot> {
+        self.contents.get_signed(row as isize, col as isize).copied()
+    }
+
+    pub fn height(&self)->usize {
+        self.contents.height()
+    }
+
+    pub fn width(&self)->usize { 
+        self.contents.width()
+    }
+
+    pub fn count_total(&self) -> usize {
+        self.contents.cells().iter().filter(|slot| match slot {
+            Slot::Empty=>false,
+            Slot::Occupied=>true
+        }).count()
+    }
+
+    fn availability_for(&self, row:usize, col:usize) -> Result<SlotMobility, Box<dyn Error>> {
+        match self.contents.get(row, col) {
+            Some(Slot::Occupied)=>{
+                    let surrounding_count = self.contents.neighbour_cells(row, col, Neighbourhood::Eight)
+                        .filter(|s| matches!(s, Slot::Occupied))
+                        .count();
+                    //Instructions say that if there are less than for adjacent occupied slots, the slot is accessible
+                    if surrounding_count < 4 {
+                        Ok(SlotMobility::Accessible)
+                    } else {
+                        Ok(SlotMobility::Immovable)
+                    }
+            },
+            Some(Slot::Empty)=> Ok(SlotMobility::Empty),
+            None=>Err("Grid was improperly shaped".into())
+        }
+    }
+
+    pub fn map_accessible(&self) -> Result<WarehouseAvailability, Box<dyn Error>> {
+        if self.contents.cells().is_empty() {
+            return Err("there was no content to search".into())
+        }
+        let cells:Result<Vec<SlotMobility>, Box<dyn Error>> = self.contents.iter()
+            .map(|((row, col), _)| self.availability_for(row, col))
+            .collect();
+        Ok(WarehouseAvailability { contents: Grid::from_cells(self.width(), cells?)? })
+    }
+
+    /**
+     * The forklifts can only access a roll of paper if there are fewer than four rolls of paper in the eight adjacent positions. 
+     * Count how many occupied slots have less than 4 rolls of paper around them
+     */
+    pub fn count_accessible(&self) -> Result<usize, Box<dyn Error>> {
+        Ok(self.map_accessible()?.contents.cells().iter().filter(|slot| **slot==SlotMobility::Accessible).count())
+    }
+    
+    pub fn render(&self) -> String {
+        self.contents.render(Slot::to_char)
+    }
+}
+
+impl WarehouseAvailability {
+    pub fn at(&self, row:usize, col:usize) -> Option<SlotMobility> {
+        self.contents.get(row, col).copied()
+    }
+
+    pub fn height(&self)->usize {
+        self.contents.height()
+    }
+
+    pub fn width(&self)->usize { 
+        self.contents.width()
+    }
+
+    pub fn render(&self) -> String {
+        self.contents.render(SlotMobility::to_char)
+    }
+
+    /**
+     * Removes the accessible rolls and returns the new warehouse state
+     */
+    pub fn next_state(&self) -> WarehouseGrid {
+        let cells:Vec<Slot> = self.contents.cells().par_iter().map(|slot| match slot {
+            SlotMobility::Empty=>Slot::Empty,
+            SlotMobility::Accessible=>Slot::Empty,
+            SlotMobility::Immovable=>Slot::Occupied
+        }).collect();
+
+        //the cells came from a grid of the same width, so they always fit
+        WarehouseGrid { contents: Grid::from_cells(self.width(), cells).unwrap() }
+    }
+}
+
+/**
+ * The worked example of the warehouse floor from the puzzle text
+ */
+pub const EXAMPLE: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+/**
+ * Part 1 counts the rolls that are accessible straight away, part 2 keeps removing accessible rolls until
+ * none are left and counts how many were moved in total
+ */
+#[derive(Default)]
+pub struct Day4;
+
+impl Puzzle for Day4 {
+    type Input = WarehouseGrid;
+    type Output = usize;
+
+    fn parse(&self, input:&str) -> Result<WarehouseGrid, Box<dyn Error>> {
+        WarehouseGrid::from_string(input)
+    }
+
+    fn part1(&self, input:&WarehouseGrid) -> Result<usize, Box<dyn Error>> {
+        input.count_accessible()
+    }
+
+    fn part2(&self, input:&WarehouseGrid) -> Result<usize, Box<dyn Error>> {
+        let mut grid = input.clone();
+        let mut total_moved = 0;
+        loop {
+            let accessible_count = grid.count_accessible()?;
+            if accessible_count==0 {
+                break;
+            }
+            total_moved += accessible_count;
+            debug!(removed = accessible_count, total_moved, "removed a round of accessible rolls");
+            grid = grid.map_accessible()?.next_state();
+        }
+        Ok(total_moved)
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "13", part2: "43" }
+    }
+
+    fn details(&self, input:&WarehouseGrid) -> Result<Value, Box<dyn Error>> {
+        Ok(json!({ "width": input.width(), "height": input.height(), "rolls": input.count_total() }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::assert_snapshot;
+
+
+    #[test]
+    fn test_example() {
+        let grid_desc = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+        let grid = WarehouseGrid::from_string(grid_desc).unwrap();
+        assert_eq!(grid.height(), 10);
+        assert_eq!(grid.width(), 10);
+
+        assert_eq!(grid.count_accessible().unwrap(), 13);
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let grid = Day4.parse(EXAMPLE).unwrap();
+        assert_eq!(Day4.part1(&grid).unwrap(), 13);
+        assert_eq!(Day4.part2(&grid).unwrap(), 43);
+    }
+
+    #[test]
+    fn test_at() {
+        let grid_desc = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+        let grid = WarehouseGrid::from_string(grid_desc).unwrap();
+        assert_eq!(grid.at(0, 0), Some(Slot::Empty));
+        assert_eq!(grid.at(0, 1), Some(Slot::Empty));
+        assert_eq!(grid.at(0, 2), Some(Slot::Occupied));
+        assert_eq!(grid.at(0, 3), Some(Slot::Occupied));
+        assert_eq!(grid.at(0, 4), Some(Slot::Empty));
+    }
+
+    #[test]
+    fn test_read() {
+        let grid_desc = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        let grid = WarehouseGrid::from_string(grid_desc).unwrap();
+        assert_eq!(grid.render(), grid_desc);
+    }
+
+    #[test]
+    fn test_showmap() {
+        let grid_desc = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+        let grid = WarehouseGrid::from_string(grid_desc).unwrap();
+        assert_eq!(grid.height(), 10);
+        assert_eq!(grid.width(), 10);
+
+        let availability = grid.map_accessible().unwrap();
+        println!("{}", availability.render());
+    }
+
+    #[test]
+    fn test_state_removal() {
+        let initial_grid = WarehouseGrid::from_string(EXAMPLE).unwrap();
+        let availability = initial_grid.map_accessible().unwrap();
+        assert_snapshot!("example_availability_1", availability.render());
+        assert_eq!(initial_grid.count_accessible().unwrap(), 13);
+
+        let next_grid = availability.next_state();
+        assert_eq!(next_grid.count_accessible().unwrap(), 12);
+        assert_snapshot!("example_availability_2", next_grid.map_accessible().unwrap().render());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = WarehouseGrid::from_string("..@@.\n@@#@.\n").err().unwrap();
+        assert_eq!(e.to_string(), "line 2, column 3: Unparseable character '#'
+  @@#@.
+    ^");
+    }
+}
@@ -0,0 +1,62 @@
+use common::Puzzle;
+use day4::{generate, Day4};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+//Plain index loops keep this obviously the same as the puzzle text, which is the point of a brute force
+#[allow(clippy::needless_range_loop)]
+fn accessible(rolls:&[Vec<bool>]) -> Vec<(usize, usize)> {
+    let mut result = vec![];
+    for row in 0..rolls.len() {
+        for col in 0..rolls[row].len() {
+            if !rolls[row][col] {
+                continue;
+            }
+            let mut neighbours = 0;
+            for r in row.saturating_sub(1)..=(row+1).min(rolls.len()-1) {
+                for c in col.saturating_sub(1)..=(col+1).min(rolls[row].len()-1) {
+                    if (r, c)!=(row, col) && rolls[r][c] {
+                        neighbours += 1;
+                    }
+                }
+            }
+            if neighbours < 4 {
+                result.push((row, col));
+            }
+        }
+    }
+    result
+}
+
+/**
+ * Counts the accessible rolls straight away, then removes them round by round until none are left
+ */
+fn brute_force(input:&str) -> (usize, usize) {
+    let mut rolls:Vec<Vec<bool>> = input.lines().map(|line| line.chars().map(|ch| ch=='@').collect()).collect();
+    let first = accessible(&rolls).len();
+    let mut removed = 0;
+    loop {
+        let round = accessible(&rolls);
+        if round.is_empty() {
+            break;
+        }
+        removed += round.len();
+        for (row, col) in round {
+            rolls[row][col] = false;
+        }
+    }
+    (first, removed)
+}
+
+proptest! {
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>(), width in 1_usize..20, height in 1_usize..20, density in 0.0..1.0) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, width, height, density);
+        let grid = Day4.parse(&input).unwrap();
+
+        let (first, removed) = brute_force(&input);
+        prop_assert_eq!(Day4.part1(&grid).unwrap(), first, "input:\n{}", input);
+        prop_assert_eq!(Day4.part2(&grid).unwrap(), removed, "input:\n{}", input);
+    }
+}
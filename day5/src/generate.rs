@@ -0,0 +1,22 @@
+use rand::Rng;
+
+/**
+ * A random inventory: a list of fresh ID ranges, a blank line, then a list of ingredient IDs.
+ * Ranges are kept short and close together so that plenty of them overlap
+ */
+pub fn input(rng:&mut impl Rng, max_ranges:usize, max_ids:usize, max_id:u64) -> String {
+    let range_count = rng.random_range(1..=max_ranges);
+    let id_count = rng.random_range(1..=max_ids);
+
+    let mut result = String::new();
+    for _ in 0..range_count {
+        let start = rng.random_range(0..=max_id);
+        let end = start + rng.random_range(0..=max_id / 5);
+        result.push_str(&format!("{}-{}\n", start, end));
+    }
+    result.push('\n');
+    for _ in 0..id_count {
+        result.push_str(&format!("{}\n", rng.random_range(0..=max_id + max_id / 5)));
+    }
+    result
+}
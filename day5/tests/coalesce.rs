@@ -0,0 +1,16 @@
+use day5::{coalesce_overlapping_ranges, ProductIdRange};
+
+#[test]
+fn test_coalesce_from_outside_the_crate() {
+    let ranges = vec![
+        ProductIdRange::new(10, 14),
+        ProductIdRange::new(3, 5),
+        ProductIdRange::new(16, 20),
+        ProductIdRange::new(12, 18),
+    ];
+    let coalesced = coalesce_overlapping_ranges(ranges);
+    assert_eq!(coalesced, vec![ProductIdRange::new(3, 5), ProductIdRange::new(10, 20)]);
+    assert_eq!(coalesced[1].start(), 10);
+    assert_eq!(coalesced[1].end(), 20);
+    assert_eq!(coalesced.iter().map(|r| r.size()).sum::<u64>(), 14);
+}
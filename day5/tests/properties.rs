@@ -0,0 +1,41 @@
+use std::collections::HashSet;
+use common::Puzzle;
+use day5::{coalesce_overlapping_ranges, generate, Day5};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+proptest! {
+    //parsing compiles a regex per line, which is slow without optimisations, so keep the runs small
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, 12, 20, 200);
+        let inventory = Day5.parse(&input).unwrap();
+        let (ranges, ids) = &inventory;
+
+        //Count fresh IDs by listing every ID in every range
+        let fresh:HashSet<u64> = ranges.iter().flat_map(|r| r.start()..=r.end()).collect();
+        let fresh_ingredients = ids.iter().filter(|id| fresh.contains(id)).count() as u64;
+
+        prop_assert_eq!(Day5.part1(&inventory).unwrap(), fresh_ingredients, "input:\n{}", input);
+        prop_assert_eq!(Day5.part2(&inventory).unwrap(), fresh.len() as u64, "input:\n{}", input);
+    }
+
+    #[test]
+    fn coalesced_ranges_are_sorted_and_separate(seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, 12, 1, 200);
+        let (ranges, _) = Day5.parse(&input).unwrap();
+
+        let before:HashSet<u64> = ranges.iter().flat_map(|r| r.start()..=r.end()).collect();
+        let coalesced = coalesce_overlapping_ranges(ranges);
+        let after:HashSet<u64> = coalesced.iter().flat_map(|r| r.start()..=r.end()).collect();
+
+        prop_assert_eq!(before, after);
+        for pair in coalesced.windows(2) {
+            prop_assert!(pair[0].end() < pair[1].start(), "{:?} overlaps {:?}", pair[0], pair[1]);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use rand::Rng;
+
+/**
+ * A random worksheet of problems side by side, one space apart.  Every problem has the same number of terms, each
+ * of up to `max_digits` digits, lined up at either the left or the right of the problem's column, with its operation
+ * on the last line under the first column.
+ *
+ * Only + and * are used, as in the puzzle, and the digits are never 0.  A short term between two longer ones leaves
+ * a space partway down a column.  There are always at least two problems, as the parser skips lines shorter than
+ * three characters
+ */
+pub fn input(rng:&mut impl Rng, max_problems:usize, max_terms:usize, max_digits:usize) -> String {
+    let problem_count = rng.random_range(2..=max_problems.max(2));
+    let term_count = rng.random_range(1..=max_terms.max(1));
+
+    //columns[i] holds the padded terms of problem i, then its operation padded to the same width
+    let columns:Vec<Vec<String>> = (0..problem_count).map(|_| {
+        let terms:Vec<String> = (0..term_count).map(|_| {
+            let digits = rng.random_range(1..=max_digits.max(1));
+            (0..digits).map(|_| char::from_digit(rng.random_range(1..=9), 10).unwrap()).collect()
+        }).collect();
+        let width = terms.iter().map(|t| t.len()).max().unwrap_or(1);
+        let left = rng.random_bool(0.5);
+        let op = if rng.random_bool(0.5) { "+" } else { "*" };
+        terms.iter()
+            .map(|t| if left { format!("{:<width$}", t) } else { format!("{:>width$}", t) })
+            .chain([format!("{:<width$}", op)])
+            .collect()
+    }).collect();
+
+    (0..=term_count).map(|row| {
+        let cells:Vec<&str> = columns.iter().map(|c| c[row].as_str()).collect();
+        cells.join(" ") + "\n"
+    }).collect()
+}
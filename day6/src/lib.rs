@@ -0,0 +1,312 @@
+use std::{error::Error, num::ParseIntError};
+use regex::Regex;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use common::{json, numbered_lines, trace, Example, Grid, ParseError, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Operation {
+    Mul,
+    Add,
+    Sub,
+    Div
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MathProblem {
+    terms: Vec<i64>,
+    op: Operation
+}
+
+impl MathProblem {
+    pub fn new(terms:Vec<i64>, op:Operation) -> MathProblem {
+        MathProblem { terms, op }
+    }
+
+    pub fn terms(&self) -> &[i64] {
+        &self.terms
+    }
+
+    pub fn op(&self) -> Operation {
+        self.op
+    }
+
+    pub fn calculate(&self) -> Option<i64> {
+        match self.op {
+            Operation::Mul=>self.terms.clone().into_iter().reduce(|total, term| total * term),
+            Operation::Add=>self.terms.clone().into_iter().reduce(|total, term| total+term),
+            Operation::Sub=>self.terms.clone().into_iter().reduce(|total, term| total - term),
+            Operation::Div=>self.terms.clone().into_iter().reduce(|total, term| total / term)
+        }
+    }
+}
+
+/**
+ * Works out the operation from its symbol, complaining about where it is if we don't know it
+ */
+fn parse_operation(line_number:usize, line:&str, symbol:&str) -> Result<Operation, ParseError> {
+    match symbol.trim() {
+        "*"=>Ok(Operation::Mul),
+        "+"=>Ok(Operation::Add),
+        "-"=>Ok(Operation::Sub),
+        "/"=>Ok(Operation::Div),
+        other=>Err(ParseError::at(line, other, format!("invalid operation specifier '{}'", other)).on_line(line_number))
+    }
+}
+
+pub fn parse_input(input:&str) -> Result<Vec<MathProblem>, Box<dyn Error>> {
+    let is_space = Regex::new("\\s+").unwrap();
+
+    //Each word keeps the line it came from, so that we can say where any problems are
+    let values:Vec<Vec<(usize, &str, &str)>> = numbered_lines(input)
+        .filter(|(_, line)| line.len()>1)
+        .map(|(line_number, line)| {
+            is_space.split(line.trim()).map(|word| (line_number, line, word)).collect()
+        })
+        .collect();
+
+    if values.is_empty() {
+        return Err("there was no data to parse".into());
+    }
+    if let Some((first, others)) = values.split_first()
+        && let Some(bad_row) = others.iter().find(|row| row.len()!=first.len()) {
+        let (line_number, line, _) = bad_row[0];
+        return Err(ParseError::whole_line(line, format!("expected {} entries to match the first line, found {}", first.len(), bad_row.len()))
+            .on_line(line_number).into());
+    }
+
+    //OK, so values currently goes row -> column (outer to inner).  We need to reverse it, into column -> row (outer to inner)
+    let new_shape = Grid::from_rows(values)?.transpose();
+    
+    //Now construct the domain objects
+    let mut results:Vec<MathProblem> = Vec::with_capacity(new_shape.height());
+    for col in new_shape.rows() {
+        match col.split_last() {
+            Some( ((line_number, line, last), others) )=>{
+                let op = parse_operation(*line_number, line, last)?;
+                let terms:Result<Vec<i64>, ParseError> = others.iter().map(|(line_number, line, s)| {
+                    s.parse::<i64>().map_err(|e| ParseError::at(line, s, format!("'{}' is not a valid number: {}", s, e)).on_line(*line_number))
+                }).collect();
+                results.push(MathProblem { op, terms: terms? });
+            },
+            None=>return Err("There were no problems to build".into())
+        }
+    }
+    Ok( results )
+}
+
+pub fn parse_input_v2(input:&str) -> Result<Vec<MathProblem>, Box<dyn Error>> {
+    let numbered:Vec<(usize, &str)> = numbered_lines(input).filter(|(_, l)| l.len()>2).collect();
+    let lines:Vec<&str> = numbered.iter().map(|(_, l)| *l).collect();
+
+    //Check the numbers up-front, while we still know where each character came from
+    if let Some((_, number_lines)) = numbered.split_last() {
+        let width = number_lines.first().map(|(_, l)| l.len()).unwrap_or(0);
+        for (line_number, line) in number_lines.iter() {
+            if let Some((col, ch)) = line.char_indices().find(|(_, ch)| !ch.is_ascii_digit() && *ch!=' ') {
+                return Err(ParseError::at(line, &line[col..col+ch.len_utf8()], format!("'{}' is not a digit", ch)).on_line(*line_number).into());
+            }
+            if line.len()!=width {
+                return Err(ParseError::whole_line(line, format!("expected {} characters to line up with the first line, found {}", width, line.len()))
+                    .on_line(*line_number).into());
+            }
+        }
+    }
+    
+    //We can use the final line (operations) as the key, since each operation char lines up with
+    //the first column of the numbers
+    let col_key:Vec<usize> = match lines.last().map(|last_line| {
+            let anychar = Regex::new("([^\\s])").unwrap();
+            anychar.captures_iter(last_line).map(|c| c.get_match().start()).collect()
+    }) {
+        Some(key)=>key,
+        None=>return Err("there was not enough data to determine a key".into())
+    };
+
+    //Now slice the lines to get the entries
+    let entries:Vec<Vec<&str>> = lines.par_iter().map(|line| {
+        let mut line_entries:Vec<&str> = Vec::with_capacity(col_key.len());
+        for i in 0..col_key.len() {
+            let from = col_key[i];
+            let to = if i==col_key.len()-1 {
+                line.len()+1
+            } else {
+                col_key[i+1]
+            };
+            line_entries.push(&line[from..to-1]);
+        }
+        line_entries
+    }).collect();
+
+    //Transpose to group them correctly
+    let new_shape_entries = Grid::from_rows(entries)?.transpose();
+    trace!(problems = new_shape_entries.height(), terms = new_shape_entries.width(), "grouped the columns into problems");
+
+    //Now we transpose again, to get numbers made from columns not rows.  This gets our actual numbers
+    let (op_line_number, op_line) = numbered[numbered.len()-1];
+    let rearranged_words_res:Result<Vec<_>, Box<dyn Error>> = new_shape_entries.rows().map(|prob| {
+        match prob.split_last() {
+            Some((op, terms))=>{
+                let per_char:Vec<Vec<char>> = terms.iter().map(|t| t.chars().collect()).collect();
+                let transposed_chars = Grid::from_rows(per_char)?.transpose();
+                //we should read from the bottom up, so reverse the order
+                let transposed_words: Vec<String> = transposed_chars.rows().map(|word| word.iter().collect()).rev().collect();
+                
+                let opsym = parse_operation(op_line_number, op_line, op)?;
+                Ok(( opsym, transposed_words ))
+            },
+            None=>Err("the problem was not correctly formatted".into())
+        }
+    }).collect();
+
+    let rearranged_words = rearranged_words_res?;
+    trace!(?rearranged_words, "read the numbers down the columns");
+
+    let result:Result<Vec<MathProblem>, ParseIntError> = rearranged_words.into_iter().map(|(op, words)| {
+        //A shorter number can sit between two longer ones, leaving a space partway down a column, which we read straight past
+        let maybe_terms:Result<Vec<i64>, ParseIntError> = words.par_iter()
+            .map(|w| w.chars().filter(|ch| *ch!=' ').collect::<String>().parse::<i64>())
+            .collect();
+
+        maybe_terms.map(|terms| MathProblem { terms, op })
+    }).collect();
+
+    //We've already checked every character, so the only thing left to go wrong is a column with no digits in it
+    result.map_err(|e| ParseError::whole_line(op_line, format!("a problem has a column with no digits in it: {}", e)).on_line(op_line_number).into())
+}
+
+/**
+ * The worked example of the maths worksheet from the puzzle text
+ */
+pub const EXAMPLE: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  
+";
+
+/**
+ * The two parts read the same worksheet differently, so both interpretations are parsed up-front:
+ * row-wise numbers for part 1 and column-wise numbers for part 2
+ */
+#[derive(Default)]
+pub struct Day6;
+
+fn grand_total(problems:&[MathProblem]) -> Result<i64, Box<dyn Error>> {
+    problems.iter()
+        .map(|p| p.calculate().ok_or_else(|| "missing content for a problem?".into()))
+        .sum()
+}
+
+impl Puzzle for Day6 {
+    type Input = (Vec<MathProblem>, Vec<MathProblem>);
+    type Output = i64;
+
+    fn parse(&self, input:&str) -> Result<(Vec<MathProblem>, Vec<MathProblem>), Box<dyn Error>> {
+        Ok( (parse_input(input)?, parse_input_v2(input)?) )
+    }
+
+    fn part1(&self, input:&(Vec<MathProblem>, Vec<MathProblem>)) -> Result<i64, Box<dyn Error>> {
+        grand_total(&input.0)
+    }
+
+    fn part2(&self, input:&(Vec<MathProblem>, Vec<MathProblem>)) -> Result<i64, Box<dyn Error>> {
+        grand_total(&input.1)
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "4277556", part2: "3263827" }
+    }
+
+    fn details(&self, input:&(Vec<MathProblem>, Vec<MathProblem>)) -> Result<Value, Box<dyn Error>> {
+        Ok(json!({ "problems": input.0.len() }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example_parse() {
+        let input = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+
+        let probs = parse_input(input).unwrap();
+        assert_eq!(probs[0], MathProblem { terms: vec![123, 45, 6], op: Operation::Mul});
+        assert_eq!(probs[1], MathProblem { terms: vec![328, 64, 98], op: Operation::Add});
+        assert_eq!(probs[2], MathProblem { terms: vec![51, 387, 215], op: Operation::Mul});
+        assert_eq!(probs[3], MathProblem { terms: vec![64, 23, 314], op: Operation::Add});
+    }
+
+    #[test]
+    fn test_example() {
+                let input = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+
+        let probs = parse_input(input).unwrap();
+        let final_result:i64 = probs.par_iter().map(|p| p.calculate().expect("the problem was empty?")).sum();
+        assert_eq!(final_result, 4277556);
+    }
+
+    #[test]
+    fn test_example_parse_v2() {
+        let input = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+        let probs = parse_input_v2(input).unwrap();
+
+        assert_eq!(probs[3], MathProblem { terms: vec![4, 431, 623], op: Operation::Add});
+        assert_eq!(probs[2], MathProblem { terms: vec![175, 581, 32], op: Operation::Mul});
+        assert_eq!(probs[1], MathProblem { terms: vec![8, 248, 369], op: Operation::Add});
+        assert_eq!(probs[0], MathProblem { terms: vec![356, 24, 1], op: Operation::Mul});
+    }
+
+    #[test]
+    fn test_example_v2() {
+        let input = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+        let probs = parse_input_v2(input).unwrap();
+        let final_result:i64 = probs.par_iter().map(|p| p.calculate().expect("the problem was empty?")).sum();
+        assert_eq!(final_result, 3263827);
+    }
+
+    #[test]
+    fn test_gaps_down_a_column() {
+        //The 3 leaves a gap in the second column of the first problem, and the 23 sticks out of the second
+        let probs = parse_input_v2("12  1\n3  23\n45  4\n+  * \n").unwrap();
+        assert_eq!(probs[0], MathProblem { terms: vec![25, 134], op: Operation::Add });
+        assert_eq!(probs[1], MathProblem { terms: vec![134, 2], op: Operation::Mul });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_input("123 328\n 45 6x\n*   +\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 5: '6x' is not a valid number: invalid digit found in string
+   45 6x
+      ^^");
+        let e = parse_input("123 328\n 45\n*   +\n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.message.as_str()), (2, "expected 2 entries to match the first line, found 1"));
+        let e = parse_input("123 328\n*   %\n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column), (2, 5));
+
+        let e = parse_input_v2("123 328\n 45 6x4\n*   +  \n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 6, "'x' is not a digit"));
+        let e = parse_input_v2("123 328\n 45 64\n*   +  \n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(e.line, 2);
+        let e = parse_input_v2("123 328\n 45 641\n*   ?  \n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column), (3, 5));
+    }
+}
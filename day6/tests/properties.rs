@@ -0,0 +1,68 @@
+use common::Puzzle;
+use day6::{generate, Day6, EXAMPLE};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn apply(op:char, terms:impl Iterator<Item = i64>) -> i64 {
+    match op {
+        '+'=>terms.sum(),
+        '*'=>terms.product(),
+        other=>panic!("the generator doesn't write {}", other)
+    }
+}
+
+/**
+ * Works out both grand totals straight from the characters.  Part 1 reads the words on each line, and part 2 finds
+ * the problems as runs of columns that aren't all spaces, reading a number down each column
+ */
+fn brute_force(input:&str) -> (i64, i64) {
+    let words:Vec<Vec<&str>> = input.lines().map(|l| l.split_whitespace().collect()).collect();
+    let (op_words, number_words) = words.split_last().unwrap();
+    let part1 = op_words.iter().enumerate()
+        .map(|(i, op)| apply(op.chars().next().unwrap(), number_words.iter().map(|row| row[i].parse::<i64>().unwrap())))
+        .sum();
+
+    let lines:Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+    let (ops, numbers) = lines.split_last().unwrap();
+    let at = |line:&Vec<char>, x:usize| line.get(x).copied().unwrap_or(' ');
+    let width = lines.iter().map(|l| l.len()).max().unwrap();
+    let mut part2 = 0;
+    let mut x = 0;
+    while x < width {
+        let start = x;
+        while x < width && lines.iter().any(|l| at(l, x)!=' ') {
+            x += 1;
+        }
+        if x > start {
+            let op = (start..x).map(|c| at(ops, c)).find(|ch| *ch!=' ').unwrap();
+            let terms = (start..x).map(|c| {
+                let digits:String = numbers.iter().map(|l| at(l, c)).filter(|ch| *ch!=' ').collect();
+                digits.parse::<i64>().unwrap()
+            });
+            part2 += apply(op, terms);
+        }
+        x += 1;
+    }
+    (part1, part2)
+}
+
+proptest! {
+    //parsing compiles a regex per line, which is slow without optimisations, so keep the runs small
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>()) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, 8, 4, 4);
+        let worksheet = Day6.parse(&input).unwrap();
+
+        let (part1, part2) = brute_force(&input);
+        prop_assert_eq!(Day6.part1(&worksheet).unwrap(), part1, "input:\n{}", input);
+        prop_assert_eq!(Day6.part2(&worksheet).unwrap(), part2, "input:\n{}", input);
+    }
+}
+
+#[test]
+fn brute_force_matches_example() {
+    assert_eq!(brute_force(EXAMPLE), (4277556, 3263827));
+}
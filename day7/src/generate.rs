@@ -0,0 +1,31 @@
+use rand::Rng;
+
+/**
+ * A random tachyon manifold shaped like the puzzle's: the beam enters on the top row, and every other row after
+ * that may hold splitters, which are never next to each other or on the edge.  The last row is always empty
+ */
+pub fn input(rng:&mut impl Rng, width:usize, splitter_rows:usize, density:f64) -> String {
+    let width = width.max(3);
+    let mut rows:Vec<String> = vec![];
+
+    let mut entry = vec!['.'; width];
+    entry[rng.random_range(1..width-1)] = 'S';
+    rows.push(entry.into_iter().collect());
+    rows.push(".".repeat(width));
+
+    for _ in 0..splitter_rows {
+        let mut row = vec!['.'; width];
+        let mut col = 1;
+        while col < width-1 {
+            if rng.random_bool(density) {
+                row[col] = '^';
+                col += 2;
+            } else {
+                col += 1;
+            }
+        }
+        rows.push(row.into_iter().collect());
+        rows.push(".".repeat(width));
+    }
+    rows.join("\n") + "\n"
+}
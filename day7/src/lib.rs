@@ -0,0 +1,246 @@
+use std::{error::Error, fmt::Display};
+use common::{json, trace, Example, Grid, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EnvironmentCell {
+    Empty,
+    Occupied(usize),
+    BeamEntry,
+    Splitter
+}
+
+impl EnvironmentCell {
+    pub fn new(ch:char) -> Result<Self, Box<dyn Error>> {
+        match ch {
+            '.'=>Ok(Self::Empty),
+            '|'=>Ok(Self::Occupied(1)),
+            'S'=>Ok(Self::BeamEntry),
+            '^'=>Ok(Self::Splitter),
+            _=>Err(format!("invalid symbol {}", ch).into())
+        }
+    }
+    pub fn to_char(&self) -> char {
+        match self {
+            Self::Empty=>'.',
+            Self::Occupied(_)=>'|',
+            Self::BeamEntry=>'S',
+            Self::Splitter=>'^'
+        }
+    }
+    pub fn add_beams(&self, new:usize)->Self{
+        match self {
+            Self::Occupied(n)=>Self::Occupied(*n+new),
+            _=>Self::Occupied(new)
+        }
+    }
+
+}
+
+#[derive(Clone)]
+pub struct Environment {
+    space: Grid<EnvironmentCell>,
+    pub split_count: usize,
+}
+
+impl Environment {
+    /**
+     * Parse a new Environment object from string 
+     * */
+    pub fn from_string(input:&str) -> Result<Environment, Box<dyn Error>> {
+        //Every line must be the same width, so our assumptions during propagation hold
+        let space = Grid::parse(input, EnvironmentCell::new)?;
+        Ok(Environment { space, split_count: 0, })
+    }
+
+    pub fn width(&self) -> usize {
+        self.space.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.space.height()
+    }
+
+    /**
+     * Adds beams to the cell at (row, col), if there is such a cell
+     */
+    fn add_beams(&mut self, row:usize, col:usize, new:usize) {
+        if let Some(cell) = self.space.get_mut(row, col) {
+            *cell = cell.add_beams(new);
+        }
+    }
+
+    /**
+     * Mutates the space, representing propagation from (row) to (row+1)
+     */
+    pub fn propagate(&mut self, row:usize) -> Result<(), Box<dyn Error>> {
+        if row+1 >= self.space.height() {
+            return Err(format!("row {} is out of range", row).into())
+        }
+
+        //We already know that all the rows are equal length, as this is ensured in the parsing method
+        for col in 0..self.width() {
+            match self.space.get(row, col).copied() {
+                Some(EnvironmentCell::BeamEntry)=>{
+                    //cheat;  we should really check if there is a splitter just below but in our provided data we know that there isn't
+                    self.space.set(row+1, col, EnvironmentCell::Occupied(1))?;
+                },
+                //  Normally the space below gets occupied, unless there is a splitter in which case n-1 and n+1 get occupied (splitter stays)
+                Some(EnvironmentCell::Occupied(current_timelines))=>{
+                    if self.space.get(row+1, col) == Some(&EnvironmentCell::Splitter) {
+                        self.split_count+=1;
+                        if col>0 {
+                            self.add_beams(row+1, col-1, current_timelines);
+                        }
+                        self.add_beams(row+1, col+1, current_timelines);
+                    } else {
+                        self.add_beams(row+1, col, current_timelines);
+                    }
+                },
+                //In these cases the space below remains the same
+                Some(EnvironmentCell::Empty)=> { },
+                Some(EnvironmentCell::Splitter)=>{ },
+                None=>{ }
+            }
+        }
+        trace!(row, splits = self.split_count, "propagated the beams down a row");
+
+        Ok( () )
+    }
+
+    // pub fn count_output_beams(&self) -> usize {
+    //     self.space.last().map(|last_row| {
+    //         last_row.iter().filter(|cell| **cell==EnvironmentCell::Occupied(_)).count()
+    //     }).unwrap_or(0)
+    // }
+
+    /**
+     * Propagates the beam all the way down the space, from the entry row to the last row
+     */
+    pub fn propagate_all(&mut self) -> Result<(), Box<dyn Error>> {
+        for row in 0..self.space.height().saturating_sub(1) {
+            self.propagate(row)?;
+        }
+        Ok( () )
+    }
+
+    pub fn count_output_timelines(&self) -> Option<usize> {
+        self.space.row(self.space.height().checked_sub(1)?).map(|last_row| {
+            last_row.iter().map(|cell| match cell {
+                EnvironmentCell::Occupied(n)=>*n,
+                _=>0
+            }).sum()
+        })
+    }
+}
+
+/**
+ * For debugging, presents a string representation of the Environment
+ */
+impl Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.space.render(EnvironmentCell::to_char))
+    }
+}
+
+/**
+ * The worked example of the tachyon manifold from the puzzle text
+ */
+pub const EXAMPLE: &str = ".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+/**
+ * Part 1 counts how many times the beam is split, part 2 counts the timelines leaving the manifold
+ */
+#[derive(Default)]
+pub struct Day7;
+
+impl Puzzle for Day7 {
+    type Input = Environment;
+    type Output = usize;
+
+    fn parse(&self, input:&str) -> Result<Environment, Box<dyn Error>> {
+        Environment::from_string(input)
+    }
+
+    fn part1(&self, input:&Environment) -> Result<usize, Box<dyn Error>> {
+        let mut environment = input.clone();
+        environment.propagate_all()?;
+        Ok(environment.split_count)
+    }
+
+    fn part2(&self, input:&Environment) -> Result<usize, Box<dyn Error>> {
+        let mut environment = input.clone();
+        environment.propagate_all()?;
+        Ok(environment.count_output_timelines().unwrap_or(0))
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "21", part2: "40" }
+    }
+
+    fn details(&self, input:&Environment) -> Result<Value, Box<dyn Error>> {
+        Ok(json!({ "width": input.width(), "height": input.height() }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::{assert_snapshot, ParseError};
+
+    /**
+     * Propagates the example's beams down through the first `rows` rows
+     */
+    fn propagated_example(rows:usize) -> Environment {
+        let mut environment = Environment::from_string(EXAMPLE).unwrap();
+        for i in 0..rows {
+            environment.propagate(i).expect("propagation failed!");
+        }
+        environment
+    }
+
+    #[test]
+    fn test_example_steps() {
+        assert_snapshot!("example_step1", propagated_example(1).to_string());
+        assert_snapshot!("example_step2", propagated_example(2).to_string());
+        assert_snapshot!("example_step3", propagated_example(3).to_string());
+    }
+
+    #[test]
+    fn test_example_full() {
+        let steps = EXAMPLE.lines().count()-1;    //we can't propagate the last line as it has nowhere to go
+        let environment = propagated_example(steps);
+
+        assert_snapshot!("example_full", environment.to_string());
+        assert_eq!(environment.split_count, 21);
+        assert_eq!(environment.count_output_timelines(), Some(40));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = Environment::from_string("...S...\n.......\n...^..x\n").err().unwrap();
+        assert_eq!(e.to_string(), "line 3, column 7: invalid symbol x
+  ...^..x
+        ^");
+        let e = Environment::from_string("...S...\n\n......\n").err().unwrap();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column, e.width), (3, 1, 6));
+    }
+}
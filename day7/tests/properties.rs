@@ -0,0 +1,62 @@
+use common::Puzzle;
+use day7::{generate, Day7};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/**
+ * Follows every timeline separately from (row, col) to the bottom, returning how many there are
+ */
+fn timelines(rows:&[Vec<char>], row:usize, col:usize) -> usize {
+    if row+1 >= rows.len() {
+        return 1
+    }
+    if rows[row+1][col]=='^' {
+        let left = if col > 0 { timelines(rows, row+1, col-1) } else { 0 };
+        let right = if col+1 < rows[row].len() { timelines(rows, row+1, col+1) } else { 0 };
+        left + right
+    } else {
+        timelines(rows, row+1, col)
+    }
+}
+
+/**
+ * Counts the splitters that a beam reaches, walking down the manifold a row at a time
+ */
+fn splits(rows:&[Vec<char>], start:usize) -> usize {
+    let mut beams = vec![start];
+    let mut count = 0;
+    for row in rows.iter().skip(1) {
+        let mut next = vec![];
+        for col in beams {
+            if row[col]=='^' {
+                count += 1;
+                if col > 0 {
+                    next.push(col-1);
+                }
+                if col+1 < row.len() {
+                    next.push(col+1);
+                }
+            } else {
+                next.push(col);
+            }
+        }
+        next.sort();
+        next.dedup();
+        beams = next;
+    }
+    count
+}
+
+proptest! {
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>(), width in 3_usize..16, splitter_rows in 0_usize..7, density in 0.0..1.0) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, width, splitter_rows, density);
+        let environment = Day7.parse(&input).unwrap();
+
+        let rows:Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let start = rows[0].iter().position(|ch| *ch=='S').unwrap();
+        prop_assert_eq!(Day7.part1(&environment).unwrap(), splits(&rows, start), "input:\n{}", input);
+        prop_assert_eq!(Day7.part2(&environment).unwrap(), timelines(&rows, 0, start), "input:\n{}", input);
+    }
+}
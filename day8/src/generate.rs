@@ -0,0 +1,16 @@
+use rand::Rng;
+
+/**
+ * A random list of junction box positions, one `x,y,z` per line.  No two boxes share a position, but nothing stops
+ * two pairs of boxes being the same distance apart
+ */
+pub fn input(rng:&mut impl Rng, count:usize, max_coord:u64) -> String {
+    let mut boxes:Vec<(u64, u64, u64)> = Vec::with_capacity(count);
+    while boxes.len() < count {
+        let b = (rng.random_range(0..=max_coord), rng.random_range(0..=max_coord), rng.random_range(0..=max_coord));
+        if !boxes.contains(&b) {
+            boxes.push(b);
+        }
+    }
+    boxes.iter().map(|(x, y, z)| format!("{},{},{}\n", x, y, z)).collect()
+}
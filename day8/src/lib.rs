@@ -0,0 +1,628 @@
+use std::{collections::{HashMap, HashSet}, error::Error, hash::RandomState};
+use uuid::Uuid;
+use common::{debug, json, numbered_lines, Example, ParseError, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct JunctionBox {
+    x: u64,
+    y: u64,
+    z: u64,
+    unique_id: Uuid 
+}
+
+fn modulus_subtract(op_a: u64, op_b: u64)->u64 {
+    op_a.abs_diff(op_b)
+}
+
+impl JunctionBox {
+    /**
+     * Creates a new JunctionBox from a string of x,y,z co-ordinates
+     */
+    pub fn from_string(input: &str) -> Result<JunctionBox, Box<dyn Error>> {
+        Ok(JunctionBox::from_line(input)?)
+    }
+
+    fn from_line(input: &str) -> Result<JunctionBox, ParseError> {
+        let coords_res:Result<Vec<u64>, ParseError> = input.split(",").map(|num| {
+            num.parse::<u64>().map_err(|e| ParseError::at(input, num, format!("'{}' is not a valid co-ordinate: {}", num, e)))
+        }).collect();
+        
+        coords_res.and_then(|coords| if coords.len()==3 {
+            Ok(JunctionBox { x: coords[0], y: coords[1], z: coords[2], unique_id: Uuid::new_v4() })
+        } else {
+            Err(ParseError::whole_line(input, format!("expected 3 co-ordinates, found {}", coords.len())))
+        })
+    }
+
+    /**
+     * Calculates Euclidean distance between two junction boxes
+     */
+    pub fn distance(&self, other:&JunctionBox) -> f64 {
+        let total: f64 = [
+            modulus_subtract(self.x, other.x),
+            modulus_subtract(self.y, other.y),
+            modulus_subtract(self.z, other.z)
+        ].iter().map(|n| n.pow(2) as f64).sum::<f64>();
+        total.sqrt()
+    } 
+
+    pub fn coord(&self) -> String {
+        format!("{},{},{}", self.x, self.y, self.z)
+    }
+
+    pub fn x(&self) -> u64 {
+        self.x
+    }
+
+    pub fn y(&self) -> u64 {
+        self.y
+    }
+
+    pub fn z(&self) -> u64 {
+        self.z
+    }
+}
+
+#[derive(PartialEq, Debug, Eq)]
+pub struct JunctionBoxPair<'a> {
+    box_one: &'a JunctionBox,
+    box_two: &'a JunctionBox
+}
+
+impl JunctionBoxPair<'_> {
+    pub fn distance(&self) ->f64 {
+        self.box_one.distance(self.box_two)
+    }
+
+    pub fn box_one(&self) -> &JunctionBox {
+        self.box_one
+    }
+
+    pub fn box_two(&self) -> &JunctionBox {
+        self.box_two
+    }
+}
+
+impl PartialOrd for JunctionBoxPair<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JunctionBoxPair<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self.distance() < other.distance() {
+            std::cmp::Ordering::Less
+        } else if self.distance() > other.distance() {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }
+}
+
+/**
+ * This object tracks the circuits, in the form of an association table and a set of the valid circuit IDs
+ */
+#[derive(Debug)]
+pub struct Circuits {
+    //a HashMap, linking box ID on the left to circuit ID on the right. (many-to-one)
+    memberships: HashMap<Uuid, Uuid>,
+    //the inverse of `memberships`, linking circuit ID on the left to box IDs on the right (one-to-many)
+    circuit_memberships: HashMap<Uuid, HashSet<Uuid>>,
+    valid_circits: HashSet<Uuid>,
+    all_boxes: HashMap<Uuid, JunctionBox>
+}
+
+impl Circuits {
+    pub fn new(boxes:&[JunctionBox])->Circuits {
+        Circuits { 
+            memberships: HashMap::new(), 
+            circuit_memberships: HashMap::new(),
+            valid_circits: HashSet::new(),
+            all_boxes: HashMap::from_iter(boxes.iter().map(|b| (b.unique_id, b.to_owned())))
+        }
+    }
+
+    pub fn circuit_for(&self, bx:&JunctionBox) -> Option<Uuid> {
+        self.memberships.get(&bx.unique_id).cloned()
+    }
+
+    pub fn all_circuits(&self) -> impl Iterator<Item = (&Uuid, &HashSet<Uuid>)> {
+        self.circuit_memberships.iter()
+    }
+
+    pub fn all_connected_boxes(&self) -> impl Iterator<Item = &JunctionBox> {
+        self.memberships.keys().filter_map(|k| self.all_boxes.get(k))
+    }
+
+    pub fn disconnected_boxes(&self) -> Vec<&JunctionBox> {
+        let connected_ids:HashSet<&Uuid, RandomState> = HashSet::from_iter(self.memberships.keys());
+        let all_ids = HashSet::from_iter(self.all_boxes.keys());
+        all_ids.difference(&connected_ids).filter_map(|id| self.all_boxes.get(id)).collect()
+    }
+
+    pub fn sorted_circuits(&self) -> Vec<HashSet<Uuid>> {
+        let mut temp:Vec<HashSet<Uuid>> = self.circuit_memberships.values().map(|v| v.to_owned()).collect();
+        temp.sort_by(|a, b| {
+            a.len().cmp(&b.len())
+        });
+        temp
+    }
+
+    pub fn count(&self) -> usize {
+        self.all_circuits().count()
+    }
+
+    pub fn connect_pair(&mut self, pair:&JunctionBoxPair) -> Option<Uuid> {
+        self.connect(pair.box_one, pair.box_two)
+    }
+
+    /**
+     * Associates the two boxes given to a circuit and returns the ID of that circuit.
+     * Returns the id of the circuit, or None if they were already joined
+     */
+    pub fn connect(&mut self, box_a:&JunctionBox, box_b:&JunctionBox) -> Option<Uuid> {
+        // {} -> {}", box_a.coord(), box_b.coord());
+        match (self.circuit_for(box_a), self.circuit_for(box_b)) {
+            (None, None)=>{
+                //Neither box is in a circuit; create a new circuit ID and associate them
+                let c_id = Uuid::new_v4();
+                self.valid_circits.insert(c_id);
+                self.memberships.insert(box_a.unique_id, c_id);
+                self.memberships.insert(box_b.unique_id, c_id);
+                match self.circuit_memberships.get_mut(&c_id) {
+                    Some(existing_content)=>{
+                        existing_content.insert(box_a.unique_id);
+                        existing_content.insert(box_b.unique_id);
+                    },
+                    None=>{
+                        let mut m:HashSet<Uuid> = HashSet::with_capacity(2);
+                        m.insert(box_a.unique_id);
+                        m.insert(box_b.unique_id);
+                        self.circuit_memberships.insert(c_id, m);
+                    }
+                };
+                Some(c_id)
+            },
+            (Some(c_id), None)=>{
+                //We are joining a new box onto an existing circuit
+                self.memberships.insert(box_b.unique_id, c_id);
+                match self.circuit_memberships.get_mut(&c_id) {
+                    Some(existing_content)=>{
+                        existing_content.insert(box_b.unique_id);
+                    },
+                    None=>{
+                        let mut m:HashSet<Uuid> = HashSet::with_capacity(2);
+                        m.insert(box_b.unique_id);
+                        self.circuit_memberships.insert(c_id, m);
+                    }
+                };
+                Some(c_id)
+            },
+            (None, Some(c_id))=>{
+                //We are joining a new box onto an existing circuit
+                self.memberships.insert(box_a.unique_id, c_id);
+                match self.circuit_memberships.get_mut(&c_id) {
+                    Some(existing_content)=>{
+                        existing_content.insert(box_a.unique_id);
+                    },
+                    None=>{
+                        let mut m:HashSet<Uuid> = HashSet::with_capacity(2);
+                        m.insert(box_a.unique_id);
+                        self.circuit_memberships.insert(c_id, m);
+                    }
+                };
+                Some(c_id)
+            },
+            (Some(circuit_a), Some(circuit_b))=>{
+                if circuit_a==circuit_b {   //if they are already part of the same circuit, then we don't need to join again
+                    return None;
+                }
+                //Both boxes are members of different circuits; we must merge the circuits by removing one and moving all
+                //its contents to the other
+                self.valid_circits.remove(&circuit_b);
+                let members_to_move = self.circuit_memberships.remove(&circuit_b);
+
+                match members_to_move {
+                    Some(set)=>{
+                        set.iter().for_each(|box_id| {
+                            self.memberships.insert(*box_id, circuit_a);
+                        });
+                        match self.circuit_memberships.get_mut(&circuit_a) {
+                            Some(existing_circuit_a)=>existing_circuit_a.extend(set),
+                            None=>panic!("when merging there should already be memberships in circuit a!")
+                        }
+                    },
+                    None=>panic!("when merging there should already be memberships in circuit b!")
+                }
+                Some(circuit_a)
+            }
+        }
+    }
+}
+
+pub fn parse_input(input:&str) -> Result<Vec<JunctionBox>, Box<dyn Error>> {
+    numbered_lines(input)
+        .map(|(line_number, line)| JunctionBox::from_line(line).map_err(|e| e.on_line(line_number).into()))
+        .collect()
+}
+
+/**
+ * Generates every permutation of box pairs from the incoming list.
+ * We only permute going forwards; otherwise there would be two pairs for every box (one the mirror-image of the other)
+ */
+pub fn pair_up<'a> (boxes:&'a [JunctionBox]) -> Vec<JunctionBoxPair<'a>> {
+    let mut result:Vec<JunctionBoxPair> = vec![];
+
+    let top = boxes.len();
+    for i in 0..boxes.len() {
+        if let Some((first, others)) = boxes[i..top].split_first() {
+            for box_two in others {
+                result.push(JunctionBoxPair { box_one: first, box_two})
+            }
+        }
+    }
+
+    result
+}
+
+/**
+ * The worked example of junction box positions from the puzzle text.  Note it only uses 10 connections for part 1
+ */
+pub const EXAMPLE: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
+/**
+ * Part 1 joins the closest `connections` pairs and multiplies the sizes of the three largest circuits.
+ * Part 2 keeps joining until every box is connected and multiplies the X co-ordinates of the last pair joined.
+ * The real puzzle uses 1000 connections, the worked example only 10
+ */
+pub struct Day8 {
+    pub connections: usize
+}
+
+impl Day8 {
+    /**
+     * The puzzle configured for the worked example, which joins fewer pairs than the real puzzle
+     */
+    pub fn for_example() -> Day8 {
+        Day8 { connections: 10 }
+    }
+}
+
+impl Default for Day8 {
+    fn default() -> Self {
+        Day8 { connections: 1000 }
+    }
+}
+
+impl Puzzle for Day8 {
+    type Input = Vec<JunctionBox>;
+    type Output = u64;
+
+    fn parse(&self, input:&str) -> Result<Vec<JunctionBox>, Box<dyn Error>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input:&Vec<JunctionBox>) -> Result<u64, Box<dyn Error>> {
+        let mut pairs = pair_up(input);
+        pairs.sort();
+        if pairs.len() < self.connections {
+            return Err("there were insufficient boxes to complete the task".into());
+        }
+
+        let mut circuits = Circuits::new(input);
+        for pair in pairs.iter().take(self.connections) {
+            circuits.connect_pair(pair);
+        }
+        circuits.sorted_circuits().iter()
+            .rev()
+            .take(3)
+            .map(|c| c.len() as u64)
+            .reduce(|total, size| total*size)
+            .ok_or_else(|| "there were not enough circuits to take the product".into())
+    }
+
+    fn part2(&self, input:&Vec<JunctionBox>) -> Result<u64, Box<dyn Error>> {
+        let mut pairs = pair_up(input);
+        pairs.sort();
+
+        //Connect until all the boxes are joined into a single circuit; the answer comes from the last pair we had to join.
+        //Every box being in _a_ circuit isn't enough, as there can still be several circuits at that point
+        let mut circuits = Circuits::new(input);
+        let mut last_pair = None;
+        for pair in pairs.iter() {
+            if circuits.disconnected_boxes().is_empty() && circuits.count()==1 {
+                break;
+            }
+            circuits.connect_pair(pair);
+            last_pair = Some(pair);
+        }
+        if let Some(pair) = last_pair {
+            debug!(one = %pair.box_one.coord(), two = %pair.box_two.coord(), "last pair needed to make a single circuit");
+        }
+        last_pair
+            .map(|pair| pair.box_one.x * pair.box_two.x)
+            .ok_or_else(|| "there were no pairs to join".into())
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "40", part2: "25272" }
+    }
+
+    fn details(&self, input:&Vec<JunctionBox>) -> Result<Value, Box<dyn Error>> {
+        let mut pairs = pair_up(input);
+        pairs.sort();
+        let mut circuits = Circuits::new(input);
+        for pair in pairs.iter().take(self.connections) {
+            circuits.connect_pair(pair);
+        }
+        let circuit_sizes:Vec<usize> = circuits.sorted_circuits().iter().rev().map(|c| c.len()).collect();
+        Ok(json!({
+            "boxes": input.len(),
+            "connections": self.connections,
+            "circuit_sizes": circuit_sizes,
+            "loose_boxes": circuits.disconnected_boxes().len()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_pairup() {
+        let input = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+        let boxes = parse_input(input).unwrap();
+        let mut pairs = pair_up(&boxes);
+        println!("From {} boxes we got {} pairs", boxes.len(), pairs.len());
+        pairs.sort();
+
+        // pairs.iter().for_each(|p| {
+        //     println!("{} -> {} is {}", p.box_one.coord(), p.box_two.coord(), p.distance())
+        // });
+
+        //Based on the example data, these are the first few pairs we should have
+        assert_eq!(pairs[0].box_one.coord(), "162,817,812");
+        assert_eq!(pairs[0].box_two.coord(), "425,690,689");
+        assert_eq!(pairs[1].box_one.coord(), "162,817,812");
+        assert_eq!(pairs[1].box_two.coord(), "431,825,988");
+        assert_eq!(pairs[2].box_one.coord(), "906,360,560");
+        assert_eq!(pairs[2].box_two.coord(), "805,96,715");
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+        let boxes = parse_input(input).unwrap();
+        let mut pairs = pair_up(&boxes);
+        pairs.sort();
+
+        let mut circuits = Circuits::new(&boxes);
+
+        // circuits.connect_pair(&pairs[0]);
+        // circuits.connect_pair(&pairs[1]);
+        // circuits.connect_pair(&pairs[2]);
+        for pair in pairs.iter().take(10) {
+            circuits.connect_pair(pair);
+
+            for (i, c) in circuits.sorted_circuits().iter().enumerate() {
+                let boxes:Vec<String> = c.iter()
+                    .filter_map(|boxid| {
+                        boxes.iter().find(|b| b.unique_id==*boxid)
+                    })
+                    .map(|b| b.coord())
+                    .collect();
+
+                println!("{}: circuit of {}", i, boxes.join(";"))
+            }
+        }
+        assert_eq!(circuits.count(), 4);
+        assert_eq!(circuits.disconnected_boxes().len(), 7);
+        let circuit_sizes_sorted:Vec<usize> = circuits.sorted_circuits().iter().map(|c| c.iter().count()).collect();
+        assert_eq!(circuit_sizes_sorted, vec![2,2,4,5]);
+
+        let final_product = circuits.sorted_circuits().iter().rev()
+            .take(3)
+            .map(|c| c.len())
+            .reduce(|total, size| total*size);
+
+        assert_eq!(final_product, Some(40));
+    }
+
+    #[test]
+    fn test_connect_new() {
+        let box_a = JunctionBox::from_string("1,2,3").unwrap();
+        let box_b = JunctionBox::from_string("4,5,6").unwrap();
+        let mut c = Circuits::new(&[box_a,box_b]);
+        c.connect(&box_a, &box_b);
+
+        assert_eq!(c.count(), 1);
+        let c_id = c.circuit_for(&box_a).unwrap();
+        let content = c.circuit_memberships.get(&c_id).unwrap();
+        assert!(content.contains(&box_a.unique_id));
+        assert!(content.contains(&box_b.unique_id));
+        assert_eq!(content.len(), 2)
+    }
+
+    #[test]
+    fn test_connect_existing_l() {
+        let box_a = JunctionBox::from_string("1,2,3").unwrap();
+        let box_b = JunctionBox::from_string("4,5,6").unwrap();
+        let box_c = JunctionBox::from_string("7,8,9").unwrap();
+        let mut c = Circuits::new(&[box_a, box_b, box_c]);
+        c.connect(&box_a, &box_b);
+        c.connect(&box_b, &box_c);
+
+        assert_eq!(c.count(), 1);
+        let c_id = c.circuit_for(&box_a).unwrap();
+        let content = c.circuit_memberships.get(&c_id).unwrap();
+        assert!(content.contains(&box_a.unique_id));
+        assert!(content.contains(&box_b.unique_id));
+        assert!(content.contains(&box_c.unique_id));
+        assert_eq!(content.len(), 3)
+    }
+
+    #[test]
+    fn test_connect_existing_r() {
+        let box_a = JunctionBox::from_string("1,2,3").unwrap();
+        let box_b = JunctionBox::from_string("4,5,6").unwrap();
+        let box_c = JunctionBox::from_string("7,8,9").unwrap();
+        let mut c = Circuits::new(&[box_a, box_b, box_c]);
+        c.connect(&box_a, &box_b);
+        c.connect(&box_c, &box_b);
+
+        assert_eq!(c.count(), 1);
+        let c_id = c.circuit_for(&box_a).unwrap();
+        let content = c.circuit_memberships.get(&c_id).unwrap();
+        assert!(content.contains(&box_a.unique_id));
+        assert!(content.contains(&box_b.unique_id));
+        assert!(content.contains(&box_c.unique_id));
+        assert_eq!(content.len(), 3)
+    }
+
+    #[test]
+    fn test_connect_existing_both() {
+        let box_a = JunctionBox::from_string("1,2,3").unwrap();
+        let box_b = JunctionBox::from_string("4,5,6").unwrap();
+        let box_c = JunctionBox::from_string("7,8,9").unwrap();
+        let box_d = JunctionBox::from_string("0,1,2").unwrap();
+        
+        let mut c = Circuits::new(&[box_a, box_b, box_c, box_d]);
+        c.connect(&box_a, &box_b);
+        c.connect(&box_c, &box_d);
+        assert_eq!(c.count(), 2);
+
+        c.connect(&box_a, &box_c);
+        assert_eq!(c.count(), 1);
+        let c_id = c.circuit_for(&box_a).unwrap();
+        let content = c.circuit_memberships.get(&c_id).unwrap();
+        assert!(content.contains(&box_a.unique_id));
+        assert!(content.contains(&box_b.unique_id));
+        assert!(content.contains(&box_c.unique_id));
+        assert!(content.contains(&box_d.unique_id));
+    }
+
+    #[test]
+    fn test_connect_existing_both_same() {
+        let box_a = JunctionBox::from_string("1,2,3").unwrap();
+        let box_b = JunctionBox::from_string("4,5,6").unwrap();
+        let box_c = JunctionBox::from_string("7,8,9").unwrap();
+        let box_d = JunctionBox::from_string("0,1,2").unwrap();
+        
+        let mut c = Circuits::new(&[box_a, box_b, box_c, box_d]);
+        c.connect(&box_a, &box_b);
+        c.connect(&box_c, &box_d);
+        assert_eq!(c.count(), 2);
+
+        c.connect(&box_d, &box_c);
+        assert_eq!(c.count(), 2);
+        let c_id = c.circuit_for(&box_a).unwrap();
+        let content = c.circuit_memberships.get(&c_id).unwrap();
+        assert!(content.contains(&box_a.unique_id));
+        assert!(content.contains(&box_b.unique_id));
+        assert_eq!(content.len(), 2);
+
+        let c_id = c.circuit_for(&box_c).unwrap();
+        let content = c.circuit_memberships.get(&c_id).unwrap();
+        assert!(content.contains(&box_c.unique_id));
+        assert!(content.contains(&box_d.unique_id));
+        assert_eq!(content.len(), 2);
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let puzzle = Day8::for_example();
+        let boxes = puzzle.parse(EXAMPLE).unwrap();
+        assert_eq!(puzzle.part1(&boxes).unwrap(), 40);
+        assert_eq!(puzzle.part2(&boxes).unwrap(), 25272);
+        assert!(Day8::default().part1(&boxes).is_err());
+    }
+
+    #[test]
+    fn test_distance() {
+        let box_a = JunctionBox::from_string("0,0,0").unwrap();
+        let box_b = JunctionBox::from_string("90000,0,0").unwrap();
+        let dist = box_b.distance(&box_a);
+        assert_eq!(dist, 90000.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_input("162,817,812\n57,618,-57\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 8: '-57' is not a valid co-ordinate: invalid digit found in string
+  57,618,-57
+         ^^^");
+        let e = parse_input("162,817,812\n57,618\n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.message.as_str()), (2, "expected 3 co-ordinates, found 2"));
+    }
+
+    #[test]
+    fn test_part2_joins_every_circuit() {
+        //Once the two close pairs are joined every box is in a circuit, but there are still two circuits to join
+        let boxes = parse_input("0,0,0\n2,0,0\n1000,0,0\n1001,0,0").unwrap();
+        assert_eq!(Day8::default().part2(&boxes).unwrap(), 2000);
+    }
+}
@@ -0,0 +1,81 @@
+use common::Puzzle;
+use day8::{generate, Day8};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+type Position = (u64, u64, u64);
+
+fn squared_distance(a:&Position, b:&Position) -> u64 {
+    a.0.abs_diff(b.0).pow(2) + a.1.abs_diff(b.1).pow(2) + a.2.abs_diff(b.2).pow(2)
+}
+
+/**
+ * Every pair of boxes by index, closest first
+ */
+fn sorted_pairs(boxes:&[Position]) -> Vec<(usize, usize)> {
+    let mut pairs:Vec<(usize, usize)> = (0..boxes.len()).flat_map(|i| (i+1..boxes.len()).map(move |j| (i, j))).collect();
+    pairs.sort_by_key(|(i, j)| squared_distance(&boxes[*i], &boxes[*j]));
+    pairs
+}
+
+/**
+ * The sizes of the groups of boxes joined together by the given pairs, found by flood-filling from each box
+ */
+fn circuit_sizes(count:usize, pairs:&[(usize, usize)]) -> Vec<usize> {
+    let mut seen = vec![false; count];
+    let mut sizes = vec![];
+    for start in 0..count {
+        if seen[start] {
+            continue;
+        }
+        let mut stack = vec![start];
+        seen[start] = true;
+        let mut size = 0;
+        while let Some(current) = stack.pop() {
+            size += 1;
+            for (a, b) in pairs {
+                let other = if *a==current { *b } else if *b==current { *a } else { continue };
+                if !seen[other] {
+                    seen[other] = true;
+                    stack.push(other);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    sizes.sort();
+    sizes.reverse();
+    sizes
+}
+
+proptest! {
+    //flood-filling after every join is slow, so keep the runs small
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>(), count in 4_usize..16) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, count, 1000);
+        let boxes:Vec<Position> = input.lines().map(|line| {
+            let coords:Vec<u64> = line.split(",").map(|n| n.parse().unwrap()).collect();
+            (coords[0], coords[1], coords[2])
+        }).collect();
+        let pairs = sorted_pairs(&boxes);
+
+        //If two pairs are the same distance apart then which gets joined first is up to the sort, so skip those
+        let distances:Vec<u64> = pairs.iter().map(|(i, j)| squared_distance(&boxes[*i], &boxes[*j])).collect();
+        prop_assume!(distances.windows(2).all(|w| w[0]!=w[1]));
+
+        let connections = count;
+        let day = Day8 { connections };
+        let parsed = day.parse(&input).unwrap();
+
+        let largest:u64 = circuit_sizes(count, &pairs[0..connections]).iter().take(3).map(|s| *s as u64).product();
+        prop_assert_eq!(day.part1(&parsed).unwrap(), largest, "input:\n{}", input);
+
+        //Join the closest pairs one at a time until there is only one circuit
+        let joined = (1..=pairs.len()).find(|n| circuit_sizes(count, &pairs[0..*n]).len()==1).unwrap();
+        let (a, b) = pairs[joined-1];
+        prop_assert_eq!(day.part2(&parsed).unwrap(), boxes[a].0 * boxes[b].0, "input:\n{}", input);
+    }
+}
@@ -0,0 +1,36 @@
+use rand::Rng;
+
+/**
+ * A random rectilinear polygon of red tiles, listed in order around its edge.  It is shaped like a bar chart hanging
+ * down from the top row, with `columns` bars of random width and depth.  Neighbouring bars never have the same depth,
+ * so every tile listed is a real corner.
+ *
+ * Bars are often only one tile wide, so a shallow one between two deeper ones leaves a notch whose sides are the
+ * edges of its neighbours, with no tiles between them
+ */
+pub fn input(rng:&mut impl Rng, columns:usize, max_size:i64) -> String {
+    let columns = columns.max(1);
+    let max_size = max_size.max(2);
+
+    let mut xs:Vec<i64> = vec![0];
+    for _ in 0..columns {
+        let width = if rng.random_bool(0.3) { 1 } else { rng.random_range(1..=max_size) };
+        xs.push(xs[xs.len()-1] + width);
+    }
+    let mut depths:Vec<i64> = vec![];
+    while depths.len() < columns {
+        let depth = rng.random_range(1..=max_size);
+        if depths.last()!=Some(&depth) {
+            depths.push(depth);
+        }
+    }
+
+    //Down the left side, along the bottom of each bar, then back up the right side and along the top
+    let mut tiles:Vec<(i64, i64)> = vec![(xs[0], 0)];
+    for (i, depth) in depths.iter().enumerate() {
+        tiles.push((xs[i], *depth));
+        tiles.push((xs[i+1], *depth));
+    }
+    tiles.push((xs[columns], 0));
+    tiles.iter().map(|(x, y)| format!("{},{}\n", x, y)).collect()
+}
@@ -0,0 +1,772 @@
+use std::{collections::HashSet, error::Error, hash::RandomState};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use regex::Regex;
+use common::{json, numbered_lines, trace, warn, Example, ParseError, Puzzle, Value};
+
+pub mod generate;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Tile {
+    x: i64,
+    y: i64
+}
+
+impl Tile {
+    pub fn new(x:i64, y:i64) -> Tile {
+        Tile { x, y }
+    }
+
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+
+    pub fn y(&self) -> i64 {
+        self.y
+    }
+
+    pub fn from_string(input:&str) -> Result<Tile, Box<dyn Error>> {
+        Ok(Tile::from_line(input)?)
+    }
+
+    fn from_line(input:&str) -> Result<Tile, ParseError> {
+        let matcher = Regex::new("^\\s*(\\d+)\\s*,\\s*(\\d+)\\s*$").unwrap();
+        let coord = |s:&str| s.parse::<i64>().map_err(|e| ParseError::at(input, s, format!("'{}' is not a valid co-ordinate: {}", s, e)));
+        match matcher.captures(input).map(|c| c.extract()) {
+            Some((_, [xstr, ystr]))=>Ok(Tile { x: coord(xstr)?, y: coord(ystr)? }),
+            None=>Err(ParseError::whole_line(input, "expected a tile position like 7,1"))
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TilePair<'a> {
+    tile_a: &'a Tile,
+    tile_b: &'a Tile
+}
+
+impl TilePair<'_> {
+    pub fn new<'a>(tile_a: &'a Tile, tile_b: &'a Tile) -> TilePair<'a> {
+        TilePair { tile_a, tile_b }
+    }
+
+    pub fn tile_a(&self) -> &Tile {
+        self.tile_a
+    }
+
+    pub fn tile_b(&self) -> &Tile {
+        self.tile_b
+    }
+
+    pub fn area_of_rectangle(&self) -> u64 {
+        (
+            //(x1-x2)*(y1-y2) does not include the last row and column because the co-ordinates are exclusive.
+            //We want the area _bounded by_ the co-ordinates inclusively, i.e. include 1 extra column on the end, 
+            //and 1 extra row on the end
+            //
+            ((self.tile_a.x - self.tile_b.x).abs()+ 1) * ((self.tile_a.y - self.tile_b.y).abs() + 1)
+        ).try_into().expect("there was an integer overflow calculating area")
+    }
+
+    pub fn corners_of_rectangle(&self) -> Vec<Tile> {
+        vec![
+            Tile { x: self.tile_a.x, y: self.tile_b.y},
+            Tile { x: self.tile_a.x, y: self.tile_a.y},
+            Tile { x: self.tile_b.x, y: self.tile_a.y},
+            Tile { x: self.tile_b.x, y: self.tile_b.y}
+        ]
+    }
+}
+
+impl PartialOrd for TilePair<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TilePair<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self.area_of_rectangle() < other.area_of_rectangle() {
+            std::cmp::Ordering::Less
+        } else if self.area_of_rectangle() > other.area_of_rectangle() {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    LR, //Left->Right
+    RL, //Right-Left
+    TB, //Top-Bottom
+    BT  //Bottom-top
+}
+
+impl Direction {
+    /**
+     * "Turns" the direction 90 degrees clockwise and returns the new direction
+     */
+    pub fn turn(&self) -> Self {
+        match self {
+            Self::LR=>Self::TB,
+            Self::TB=>Self::RL,
+            Self::RL=>Self::BT,
+            Self::BT=>Self::LR
+        }
+    }
+
+    /**
+     * Returns true if this direction is the exact inverse of the one given
+     */
+    pub fn is_inverse(&self, other:&Direction) -> bool {
+        match self {
+            Self::LR=>*other==Self::RL,
+            Self::TB=>*other==Self::BT,
+            Self::RL=>*other==Self::LR,
+            Self::BT=>*other==Self::TB
+        }
+    }
+}
+
+/**
+ * Edge defines a vector that makes up a permimeter - including a direction, so we can 
+ * determine "inside" and "outside"
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge<'a> {
+    start: &'a Tile,
+    end: &'a Tile,
+    direction: Direction
+}
+
+impl Edge<'_> {
+    // /**
+    //  * Checks if a given point lies within the polygon defined by this vector.
+    //  * This assumes that the polygon was constructed in a clockwise-following manner;
+    //  * if it was anti-clockwise then the test must be reversed
+    //  */
+    // pub fn is_inside(&self, point: &Tile) -> bool {
+    //     println!("checking {:?} against {:?}", point, self);
+
+    //     match self.direction {
+    //         Direction::LR=>point.y >= self.start.y,
+    //         Direction::RL=>point.y <= self.start.y,
+    //         Direction::TB=>point.x <= self.start.x,
+    //         Direction::BT=>point.x >= self.start.x
+    //     }
+    // }
+
+    //When ray-casting, the interval must be half-open otherwise we can double count
+    pub fn x_in_range(&self, point: &Tile) -> bool {
+        let min_x = self.start.x.min(self.end.x);
+        let max_x = self.start.x.max(self.end.x);
+
+        point.x >= min_x && point.x <= max_x
+    }
+
+    pub fn y_in_range(&self, point: &Tile) -> bool {
+        let min_y = self.start.y.min(self.end.y);
+        let max_y = self.start.y.max(self.end.y);
+
+        point.y >= min_y && point.y <= max_y
+    }
+
+}
+
+/**
+ * The polygon's surroundings squashed down so that each run of rows or columns with no corner in it becomes a single
+ * row or column.  No edge starts or stops partway through a cell of the squashed grid, so every cell is either all
+ * inside the polygon or all outside it, and a flood fill from beyond the polygon finds which
+ */
+#[derive(Clone, PartialEq, Eq)]
+struct CompressedGrid {
+    /** The real columns in each column of the grid, as inclusive ranges, with an empty one either side of the polygon */
+    xs: Vec<(i64, i64)>,
+    ys: Vec<(i64, i64)>,
+    /** outside_before[j][i] is how many cells in the first j rows and first i columns are outside the polygon */
+    outside_before: Vec<Vec<u32>>
+}
+
+impl CompressedGrid {
+    /**
+     * Splits the line into one band for each co-ordinate a corner is on, and one for each run between them
+     */
+    fn bands(values:impl Iterator<Item = i64>) -> Vec<(i64, i64)> {
+        let mut values:Vec<i64> = values.collect();
+        values.sort();
+        values.dedup();
+
+        let mut bands:Vec<(i64, i64)> = vec![];
+        for v in values {
+            let next = bands.last().map(|(_, hi)| hi + 1).unwrap_or(v - 1);
+            if next < v {
+                bands.push((next, v - 1));
+            }
+            bands.push((v, v));
+        }
+        if let Some((_, hi)) = bands.last().copied() {
+            bands.push((hi + 1, hi + 1));
+        }
+        bands
+    }
+
+    fn band_of(bands:&[(i64, i64)], v:i64) -> usize {
+        bands.partition_point(|(_, hi)| *hi < v)
+    }
+
+    fn new(edges:&[Edge]) -> CompressedGrid {
+        let xs = Self::bands(edges.iter().map(|e| e.start.x));
+        let ys = Self::bands(edges.iter().map(|e| e.start.y));
+        let (width, height) = (xs.len(), ys.len());
+
+        let mut on_edge = vec![vec![false; width]; height];
+        for edge in edges {
+            let (i1, i2) = (Self::band_of(&xs, edge.start.x), Self::band_of(&xs, edge.end.x));
+            let (j1, j2) = (Self::band_of(&ys, edge.start.y), Self::band_of(&ys, edge.end.y));
+            for row in on_edge[j1.min(j2)..=j1.max(j2)].iter_mut() {
+                row[i1.min(i2)..=i1.max(i2)].fill(true);
+            }
+        }
+
+        //The first cell is beyond the polygon, so anything we can reach from it without crossing an edge is outside too
+        let mut outside = vec![vec![false; width]; height];
+        let mut to_visit:Vec<(usize, usize)> = vec![(0, 0)];
+        outside[0][0] = true;
+        while let Some((i, j)) = to_visit.pop() {
+            for (ni, nj) in [(i.wrapping_sub(1), j), (i + 1, j), (i, j.wrapping_sub(1)), (i, j + 1)] {
+                if ni < width && nj < height && !on_edge[nj][ni] && !outside[nj][ni] {
+                    outside[nj][ni] = true;
+                    to_visit.push((ni, nj));
+                }
+            }
+        }
+
+        let mut outside_before = vec![vec![0; width + 1]; height + 1];
+        for j in 0..height {
+            for i in 0..width {
+                outside_before[j+1][i+1] = outside_before[j][i+1] + outside_before[j+1][i] - outside_before[j][i] + outside[j][i] as u32;
+            }
+        }
+        CompressedGrid { xs, ys, outside_before }
+    }
+
+    /**
+     * Checks that none of the tiles in the rectangle between the two corners, which must be on corners of the polygon,
+     * are outside it
+     */
+    fn all_inside(&self, a:&Tile, b:&Tile) -> bool {
+        let (i1, i2) = (Self::band_of(&self.xs, a.x), Self::band_of(&self.xs, b.x));
+        let (j1, j2) = (Self::band_of(&self.ys, a.y), Self::band_of(&self.ys, b.y));
+        let (i1, i2, j1, j2) = (i1.min(i2), i1.max(i2) + 1, j1.min(j2), j1.max(j2) + 1);
+        let ob = &self.outside_before;
+        ob[j2][i2] + ob[j1][i1] - ob[j1][i2] - ob[j2][i1] == 0
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Perimeter<'a> {
+    edges: Vec<Edge<'a>>,
+    x_max: i64,
+    y_max: i64,
+    grid: CompressedGrid
+}
+
+impl Perimeter<'_> {
+    //Note, the set _must not be empty_ otherwise this will panic
+    fn find_topleft<'a>(set: &'a [Tile]) -> &'a Tile {
+        let mut min:&'a Tile;
+
+        match set.split_first() {
+            Some((first, others))=>{
+                min = first;
+
+                for tile in others {
+                    if tile.x <= min.x && tile.y <= min.y {
+                        min = tile;
+                    }
+                }
+            },
+            None=>panic!("cannot find topleft with an empty set")
+        }
+
+        min
+    }
+
+    fn next_controlpoint<'a>(current:&'a Tile, set:&HashSet<&'a Tile>, direction:Direction) -> Option<&'a Tile> {
+        match direction {
+            Direction::LR=>{
+                //If traversing left-right we can only move to another point on the same row (y)
+                let candidates:Vec<&&Tile> = set.iter().filter(|tile| tile.y==current.y).collect();
+
+                //Find any points to the right
+                let mut ordered_candidates:Vec<(i64, &&Tile)> = candidates.into_iter()
+                    .filter_map(|point| {
+                        let dist = point.x - current.x; //we are looking for points to the right, i.e. in increasing x
+                        if dist>0 {
+                            Some((dist, point))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                
+                //Order by distance
+                ordered_candidates.sort_by(|(dist_a, _), (dist_b, _)| {
+                    dist_a.cmp(dist_b)
+                });
+
+                //Take the first
+                ordered_candidates.first().map(|(_, tile)| &***tile)
+            },
+            Direction::RL=>{
+                //If traversing right-left we can only move to another point on the same row (y)
+                let candidates:Vec<&&Tile> = set.iter().filter(|tile| tile.y==current.y).collect();
+
+                //Find any points to the right
+                let mut ordered_candidates:Vec<(i64, &&Tile)> = candidates.into_iter()
+                    .filter_map(|point| {
+                        let dist = current.x-point.x; //we are looking for points to the left, i.e. in decreasing x
+                        if dist>0 {
+                            Some((dist, point))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                
+                //Order by distance
+                ordered_candidates.sort_by(|(dist_a, _), (dist_b, _)| {
+                    dist_a.cmp(dist_b)
+                });
+
+                //Take the first
+                ordered_candidates.first().map(|(_, tile)| &***tile)
+            },
+            Direction::TB=>{
+                //If traversing top-bottom we can only move to another point on the same column(x)
+                let candidates:Vec<&&Tile> = set.iter().filter(|tile| tile.x==current.x).collect();
+
+                let mut ordered_candidates:Vec<(i64, &&Tile)> = candidates.into_iter()
+                    .filter_map(|point| {
+                        let dist = point.y - current.y; //we are looking for points below, i.e. in increasing y
+                        if dist > 0 {
+                            Some((dist, point))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+
+                ordered_candidates.sort_by(|(dist_a, _), (dist_b, _)| {
+                    dist_a.cmp(dist_b)
+                });
+
+                ordered_candidates.first().map(|(_, tile)| &***tile)
+            },
+            Direction::BT=>{
+                //If traversing bottom-top we can only move to another point on the same column(x)
+                let candidates:Vec<&&Tile> = set.iter().filter(|tile| tile.x==current.x).collect();
+
+                let mut ordered_candidates:Vec<(i64, &&Tile)> = candidates.into_iter()
+                    .filter_map(|point| {
+                        let dist = current.y - point.y; //we are looking for points above, i.e. in decreasing y
+                        if dist > 0 {
+                            Some((dist, point))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+
+                ordered_candidates.sort_by(|(dist_a, _), (dist_b, _)| {
+                    dist_a.cmp(dist_b)
+                });
+
+                ordered_candidates.first().map(|(_, tile)| &***tile)
+            }
+        }
+    }
+
+    /**
+     * Checks if the given point is inside the polygon, or on its edge
+     */
+    pub fn sits_inside(&self, point: &Tile) -> bool {
+        let mut crossings = 0;
+
+        for edge in self.edges.iter() {
+            //We lie on the edge if we are between its start and end; this is all we need to check as the edges are axis aligned
+            if edge.x_in_range(point) && edge.y_in_range(point) {
+                return true
+            }
+
+            //We project an imaginary "ray" to the right.  This means we are only measuring intersections with the _vertical_ edges and
+            //can exclude _horizontal_ edges.  The interval must be half-open otherwise we can double count where the ray hits a corner
+            if (edge.direction==Direction::TB || edge.direction==Direction::BT) && point.x < edge.start.x
+                && ((edge.start.y <= point.y && edge.end.y > point.y) || (edge.end.y <= point.y && edge.start.y > point.y)) {
+                crossings += 1
+            }
+        }
+
+        crossings % 2 == 1
+    }
+
+    /**
+     * Checks if every tile of the rectangle is inside the polygon or on its edge.  Having all four corners inside isn't
+     * enough, as the rectangle could stretch across a notch in the polygon, or fill a notch whose sides are its own sides
+     */
+    pub fn rectangle_sits_inside(&self, pair: &TilePair) -> bool {
+        self.grid.all_inside(pair.tile_a, pair.tile_b)
+    }
+
+    /**
+     * Constructs a perimeter from the given control points
+     */
+    pub fn new<'a>(control_points: &'a [Tile]) -> Option<Perimeter<'a>> {
+        let mut edges: Vec<Edge> = vec![];
+
+        let mut cp_set:HashSet<&Tile, RandomState> = HashSet::from_iter(control_points.iter());
+        if cp_set.is_empty() {
+            return None
+        }
+
+        //Find the top-left of the incoming control points
+        let start_point = Self::find_topleft(control_points);
+        //Don't remove the initial controlpoint from the set. It should be the last one we come to.
+        let mut current = start_point;
+
+        let mut current_direction = Direction::LR;  //start going left-right
+        let mut starting_direction = current_direction;
+        while ! cp_set.is_empty() {
+            match Self::next_controlpoint(current, &cp_set, current_direction) {
+                Some(tile)=>{
+                    trace!(?tile, ?current, direction = ?current_direction, "found the next control point");
+                    //Great, we found a control point.
+                    let next_edge = Edge { start: current, end: tile, direction: current_direction };
+                    edges.push(next_edge);
+                    //If we got back to where we started, we have a perimeter!
+                    if tile == start_point {
+                        break;
+                    }
+                    //Remove this now connected control point from the set and iterate to find the next one
+                    cp_set.remove(tile);
+                    current = tile;
+                    starting_direction = current_direction;
+                    //Look for another control point 90 degrees further around the circle
+                    current_direction = current_direction.turn();
+                },
+                None=>{
+                    trace!(?current, direction = ?current_direction, starting_direction = ?starting_direction, "no control point that way");
+
+                    current_direction = current_direction.turn();
+                    if current_direction==starting_direction {
+                        //We went through 360 degrees without finding any control point to link to.
+                        //Crucially we did check if there was another point on the same line
+                        warn!(?current, remaining = cp_set.len(), "ran out of valid control points");
+                        return None
+                    } else if current_direction.is_inverse(&starting_direction) {
+                        //Don't traverse back the way we came otherwise we get stuck in a loop. Nudge on to the next possible direction
+                        current_direction = current_direction.turn();
+                    }
+                }
+            }
+        }
+
+        let x_max:i64 = control_points.iter().map(|cp| cp.x).max().unwrap();
+        let y_max:i64 = control_points.iter().map(|cp| cp.y).max().unwrap();
+        let grid = CompressedGrid::new(&edges);
+        Some(Perimeter { edges, x_max, y_max, grid })
+    }
+}
+
+pub fn pair_up<'a>(tiles: &'a [Tile]) -> Vec<TilePair<'a>> {
+    let top:usize = tiles.len();
+
+    (0_usize..top).into_par_iter()
+        .flat_map(|i| {
+            match tiles[i..top].split_first() {
+                Some((tile_a, others))=>{
+                    others.iter().map(|tile_b| {
+                        TilePair::new(tile_a, tile_b)
+                    }).collect::<Vec<TilePair>>()
+                },
+                None=>{
+                    panic!("improperly configured tile list, this should not happen");
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn parse_input(input:&str) -> Result<Vec<Tile>, Box<dyn Error>> {
+    numbered_lines(input)
+        .map(|(line_number, line)| Tile::from_line(line).map_err(|e| e.on_line(line_number).into()))
+        .collect()
+}
+
+/**
+ * The worked example of red tile positions from the puzzle text
+ */
+pub const EXAMPLE: &str = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
+/**
+ * Part 1 finds the largest rectangle between any two red tiles, part 2 the largest one that also lies
+ * entirely inside the perimeter they make
+ */
+#[derive(Default)]
+pub struct Day9;
+
+impl Puzzle for Day9 {
+    type Input = Vec<Tile>;
+    type Output = u64;
+
+    fn parse(&self, input:&str) -> Result<Vec<Tile>, Box<dyn Error>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input:&Vec<Tile>) -> Result<u64, Box<dyn Error>> {
+        pair_up(input).iter()
+            .max()
+            .map(|pair| pair.area_of_rectangle())
+            .ok_or_else(|| "the list of pairs was empty".into())
+    }
+
+    fn part2(&self, input:&Vec<Tile>) -> Result<u64, Box<dyn Error>> {
+        let perimeter = Perimeter::new(input).ok_or("could not join points into a perimeter")?;
+        pair_up(input).iter()
+            .filter(|rec| perimeter.rectangle_sits_inside(rec))
+            .max()
+            .map(|pair| pair.area_of_rectangle())
+            .ok_or_else(|| "no rectangles lay within the perimeter".into())
+    }
+
+    fn example(&self) -> Example {
+        Example { input: EXAMPLE, part1: "50", part2: "24" }
+    }
+
+    fn details(&self, input:&Vec<Tile>) -> Result<Value, Box<dyn Error>> {
+        fn describe(pair:Option<&TilePair>) -> Value {
+            match pair {
+                Some(p)=>json!({
+                    "area": p.area_of_rectangle(),
+                    "corners": p.corners_of_rectangle().iter().map(|t| [t.x, t.y]).collect::<Vec<[i64; 2]>>()
+                }),
+                None=>Value::Null
+            }
+        }
+
+        let pairs = pair_up(input);
+        let largest = pairs.iter().max();
+        let largest_inside = Perimeter::new(input)
+            .and_then(|perimeter| pairs.iter().filter(|rec| perimeter.rectangle_sits_inside(rec)).max());
+        Ok(json!({
+            "tiles": input.len(),
+            "largest_rectangle": describe(largest),
+            "largest_rectangle_inside": describe(largest_inside)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+        let mut pairs = pair_up(&tiles);
+        pairs.sort();
+
+        pairs.iter().for_each(|p| {
+            println!("({}, {}) -> ({}, {}): area {}", p.tile_a.x, p.tile_a.y, p.tile_b.x, p.tile_b.y, p.area_of_rectangle())
+        });
+        assert_eq!(pairs.last().unwrap().area_of_rectangle(), 50);
+    }
+
+    #[test]
+    fn test_find_topleft() {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+
+        let topleft = Perimeter::find_topleft(&tiles);
+        assert_eq!(topleft, &Tile{ x:7, y:1 });
+    }
+
+    #[test]
+    fn test_next_controlpoint() {
+        let input = "11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+        let tile_set: HashSet<&Tile, RandomState> = HashSet::from_iter(tiles.iter());
+
+        let next = Perimeter::next_controlpoint(&Tile{ x:7, y:1}, &tile_set, Direction::LR);
+        assert!(next.is_some());
+        let next_tile = next.unwrap();
+        assert_eq!(next_tile, &Tile { x:11, y: 1});
+    }
+
+    #[test]
+    fn test_perimeter() {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+
+        let perimeter = Perimeter::new(&tiles);
+        assert!(perimeter.is_some());
+        let perimeter = perimeter.unwrap();
+        assert_eq!(perimeter.edges.len(), 8);
+        assert_eq!(perimeter.edges[0], Edge { start: &Tile { x: 7, y: 1 }, end: &Tile { x: 11, y: 1}, direction: Direction::LR});
+        assert_eq!(perimeter.edges[1], Edge { start: &Tile { x: 11, y: 1}, end: &Tile { x: 11, y: 7}, direction: Direction::TB});
+        assert_eq!(perimeter.edges[2], Edge { start: &Tile { x: 11, y: 7 }, end: &Tile { x: 9, y: 7}, direction: Direction::RL});
+        assert_eq!(perimeter.edges[3], Edge { start: &Tile { x: 9, y: 7}, end: &Tile { x: 9, y: 5}, direction: Direction::BT});
+        assert_eq!(perimeter.edges[4], Edge { start: &Tile { x: 9, y: 5 }, end: &Tile { x:2, y: 5}, direction: Direction::RL});
+        assert_eq!(perimeter.edges[5], Edge { start: &Tile { x: 2, y: 5}, end: &Tile { x: 2, y: 3}, direction: Direction::BT});
+        assert_eq!(perimeter.edges[6], Edge { start: &Tile { x: 2, y: 3 }, end: &Tile { x: 7, y: 3}, direction: Direction::LR});
+        assert_eq!(perimeter.edges[7], Edge { start: &Tile { x: 7, y: 3}, end: &Tile { x: 7, y: 1}, direction: Direction::BT});
+    }
+
+    #[test]
+    fn test_sits_inside() {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+
+        let perimeter = Perimeter::new(&tiles);
+        assert!(perimeter.is_some());
+        let perimeter = perimeter.unwrap();
+        assert!(perimeter.sits_inside(&Tile { x: 4, y: 4}));
+        assert!(perimeter.sits_inside(&Tile { x: 10, y: 5}));
+        assert!(!perimeter.sits_inside(&Tile { x: 0, y: 0}));
+        assert!(!perimeter.sits_inside(&Tile { x: 3, y: 7}));
+    }
+
+    #[test]
+    fn test_rectangle_sits_inside() {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+
+        let perimeter = Perimeter::new(&tiles);
+        assert!(perimeter.is_some());
+        let perimeter = perimeter.unwrap();
+
+        let rec1 = TilePair::new(&Tile { x: 7, y:3 }, &Tile {x: 11, y: 1});
+        assert!(perimeter.rectangle_sits_inside(&rec1));
+        let rec2 = TilePair::new(&Tile { x: 9, y:7 }, &Tile {x: 9, y: 5});
+        assert!(perimeter.rectangle_sits_inside(&rec2));
+        let rec3 = TilePair::new(&Tile { x: 2, y:1 }, &Tile {x: 6, y: 4});
+        assert!(! perimeter.rectangle_sits_inside(&rec3));
+        let rec4 = TilePair::new(&Tile { x: 5, y:6 }, &Tile {x: 10, y: 4});
+        assert!(! perimeter.rectangle_sits_inside(&rec4));
+    }
+
+    #[test]
+    fn test_rectangle_filling_a_notch() {
+        //All four sides of the notch rectangle lie on the polygon, but its inside is outside
+        let tiles = parse_input("0,0\n0,10\n2,10\n2,2\n10,2\n10,10\n14,10\n14,0").unwrap();
+        let perimeter = Perimeter::new(&tiles).unwrap();
+
+        let notch = TilePair::new(&Tile { x: 2, y: 10 }, &Tile { x: 10, y: 2 });
+        assert!(! perimeter.rectangle_sits_inside(&notch));
+        let across_top = TilePair::new(&Tile { x: 2, y: 10 }, &Tile { x: 10, y: 10 });
+        assert!(! perimeter.rectangle_sits_inside(&across_top));
+        let along_bottom = TilePair::new(&Tile { x: 0, y: 0 }, &Tile { x: 14, y: 2 });
+        assert!(perimeter.rectangle_sits_inside(&along_bottom));
+    }
+
+    #[test]
+    fn test_rectangle_over_a_thin_notch() {
+        //The notch is one tile wide and both of its sides are edges, so every tile of it counts as inside
+        let tiles = parse_input("0,0\n0,10\n2,10\n2,2\n3,2\n3,10\n5,10\n5,0").unwrap();
+        let perimeter = Perimeter::new(&tiles).unwrap();
+
+        let whole = TilePair::new(&Tile { x: 0, y: 0 }, &Tile { x: 5, y: 10 });
+        assert!(perimeter.rectangle_sits_inside(&whole));
+        assert_eq!(Day9.part2(&tiles).unwrap(), 66);
+    }
+
+    #[test]
+    fn test_example_2() {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+        let tiles = parse_input(input).unwrap();
+        let mut pairs = pair_up(&tiles);
+        let perimeter = Perimeter::new(&tiles).expect("Could not join all points into a perimeter");
+
+        pairs.sort();
+        pairs.reverse();
+        let valid_pairs:Vec<&TilePair> = pairs.iter().filter(|rect| perimeter.rectangle_sits_inside(rect)).collect();
+
+        let result = valid_pairs.first().expect("No rectangles were valid in the perimeter");
+        assert_eq!(result.area_of_rectangle(), 24);
+    }
+
+    #[test]
+    fn test_direction_eq() {
+        assert!(Direction::LR==Direction::LR);
+        assert!(! (Direction::RL==Direction::LR));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_input("7,1\n11;1\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 1: expected a tile position like 7,1
+  11;1
+  ^^^^");
+        let e = parse_input("7,1\n11,99999999999999999999\n").unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column, e.width), (2, 4, 20));
+    }
+}
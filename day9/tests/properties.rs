@@ -0,0 +1,81 @@
+use common::Puzzle;
+use day9::{generate, Day9};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/**
+ * Marks every tile inside or on the edge of the polygon, by drawing the edges and flood-filling the outside
+ */
+fn inside_tiles(tiles:&[(i64, i64)]) -> Vec<Vec<bool>> {
+    let width = tiles.iter().map(|(x, _)| *x).max().unwrap() as usize + 3;
+    let height = tiles.iter().map(|(_, y)| *y).max().unwrap() as usize + 3;
+    //Shift everything by one, so that there is a border of outside all the way around
+    let mut edge = vec![vec![false; width]; height];
+    for (i, (x1, y1)) in tiles.iter().enumerate() {
+        let (x2, y2) = tiles[(i+1) % tiles.len()];
+        for x in (*x1).min(x2)..=(*x1).max(x2) {
+            for y in (*y1).min(y2)..=(*y1).max(y2) {
+                edge[y as usize + 1][x as usize + 1] = true;
+            }
+        }
+    }
+
+    let mut outside = vec![vec![false; width]; height];
+    let mut stack = vec![(0_usize, 0_usize)];
+    outside[0][0] = true;
+    while let Some((x, y)) = stack.pop() {
+        let neighbours = [(x.wrapping_sub(1), y), (x+1, y), (x, y.wrapping_sub(1)), (x, y+1)];
+        for (nx, ny) in neighbours {
+            if nx < width && ny < height && !outside[ny][nx] && !edge[ny][nx] {
+                outside[ny][nx] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+    (0..height-2).map(|y| (0..width-2).map(|x| !outside[y+1][x+1]).collect()).collect()
+}
+
+fn area(a:&(i64, i64), b:&(i64, i64)) -> u64 {
+    ((a.0 - b.0).abs() + 1) as u64 * ((a.1 - b.1).abs() + 1) as u64
+}
+
+/**
+ * The largest rectangles with red corners: any of them, and those with every tile inside the polygon
+ */
+fn brute_force(tiles:&[(i64, i64)]) -> (u64, u64) {
+    let inside = inside_tiles(tiles);
+    let mut largest = 0;
+    let mut largest_inside = 0;
+    for (i, a) in tiles.iter().enumerate() {
+        for b in tiles.iter().skip(i+1) {
+            largest = largest.max(area(a, b));
+            let all_inside = (a.1.min(b.1)..=a.1.max(b.1)).all(|y| {
+                (a.0.min(b.0)..=a.0.max(b.0)).all(|x| inside[y as usize][x as usize])
+            });
+            if all_inside {
+                largest_inside = largest_inside.max(area(a, b));
+            }
+        }
+    }
+    (largest, largest_inside)
+}
+
+proptest! {
+    #[test]
+    fn solver_matches_brute_force(seed in any::<u64>(), columns in 1_usize..7) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = generate::input(&mut rng, columns, 12);
+        let parsed = Day9.parse(&input).unwrap();
+        let tiles:Vec<(i64, i64)> = parsed.iter().map(|t| (t.x(), t.y())).collect();
+
+        let (largest, largest_inside) = brute_force(&tiles);
+        prop_assert_eq!(Day9.part1(&parsed).unwrap(), largest, "input:\n{}", input);
+        prop_assert_eq!(Day9.part2(&parsed).unwrap(), largest_inside, "input:\n{}", input);
+    }
+}
+
+#[test]
+fn brute_force_matches_example() {
+    let tiles:Vec<(i64, i64)> = Day9.parse(day9::EXAMPLE).unwrap().iter().map(|t| (t.x(), t.y())).collect();
+    assert_eq!(brute_force(&tiles), (50, 24));
+}